@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+use image::RgbaImage;
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use tokio::sync::Mutex;
+use crate::valorant::api;
+
+type Lookup = HashMap<String, (String, String)>;
+
+static MAPS: Lazy<Mutex<Option<Arc<Lookup>>>> = Lazy::new(|| Mutex::new(None));
+static AGENTS: Lazy<Mutex<Option<Arc<Lookup>>>> = Lazy::new(|| Mutex::new(None));
+
+fn cache_dir() -> PathBuf {
+    let exe = std::env::current_exe().unwrap_or_default();
+    exe.parent().unwrap_or(&PathBuf::from(".")).join("downfall_cache")
+}
+
+fn cache_file(url: &str) -> PathBuf {
+    let key: String = url
+        .trim_start_matches("https://")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+        .collect();
+    cache_dir().join(key)
+}
+
+pub async fn fetch_bytes(url: &str) -> Option<Vec<u8>> {
+    if url.is_empty() {
+        return None;
+    }
+
+    let path = cache_file(url);
+    if let Ok(bytes) = std::fs::read(&path) {
+        return Some(bytes);
+    }
+
+    let resp = Client::new().get(url).send().await.ok()?;
+    if !resp.status().is_success() {
+        return None;
+    }
+    let bytes = resp.bytes().await.ok()?.to_vec();
+
+    let _ = std::fs::create_dir_all(cache_dir());
+    let _ = std::fs::write(&path, &bytes);
+    Some(bytes)
+}
+
+pub async fn fetch_image(url: &str) -> Option<RgbaImage> {
+    let bytes = fetch_bytes(url).await?;
+    image::load_from_memory(&bytes).ok().map(|img| img.to_rgba8())
+}
+
+async fn lookup(cache: &Mutex<Option<Arc<Lookup>>>, fetch: impl Future<Output = Lookup>) -> Arc<Lookup> {
+    let mut cached = cache.lock().await;
+    if let Some(lookup) = cached.as_ref() {
+        return lookup.clone();
+    }
+    let lookup = Arc::new(fetch.await);
+    if !lookup.is_empty() {
+        *cached = Some(lookup.clone());
+    }
+    lookup
+}
+
+pub async fn maps() -> Arc<Lookup> {
+    lookup(&MAPS, api::fetch_map_info()).await
+}
+
+pub async fn agents() -> Arc<Lookup> {
+    lookup(&AGENTS, api::fetch_agent_map()).await
+}
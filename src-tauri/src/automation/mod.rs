@@ -0,0 +1,580 @@
+mod rules;
+mod timing;
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Mutex;
+use crate::config::{self, AppConfig, DodgeCondition};
+use crate::store::{audit, dodges, watchlist};
+use crate::store::audit::AuditEntry;
+use crate::valorant::api::{self, PregameAction};
+use crate::valorant::connection::ValorantConnection;
+use crate::valorant::score::queue_label;
+use crate::valorant::types::{AgentInfo, AuthTokens, ConnectionStatus, PregameState};
+use rules::{RuleInput, RuleMatch};
+use timing::Schedule;
+
+pub const TICK: Duration = Duration::from_millis(750);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InstalockState {
+    #[default]
+    Idle,
+    Waiting,
+    Disabled,
+    Hovering,
+    Locking,
+    Locked,
+    Failed,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalockStatus {
+    pub state: InstalockState,
+    pub match_id: String,
+    pub map_name: String,
+    pub queue_id: String,
+    pub agent_id: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DodgeState {
+    #[default]
+    Idle,
+    Waiting,
+    Disabled,
+    Safe,
+    Test,
+    Capped,
+    Dodging,
+    Dodged,
+    Failed,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DodgeStatus {
+    pub state: DodgeState,
+    pub match_id: String,
+    pub map_name: String,
+    pub queue_id: String,
+    pub rule: Option<RuleMatch>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationStatus {
+    pub instalock: InstalockStatus,
+    pub dodge: DodgeStatus,
+    pub dry_run: bool,
+}
+
+#[derive(Default)]
+struct Session {
+    match_id: String,
+    agents: Option<Vec<AgentInfo>>,
+    failed: HashSet<String>,
+    fired: HashSet<String>,
+    logged: HashSet<String>,
+    hovered: Option<String>,
+    simulated_hover: bool,
+    done: bool,
+    dodged: bool,
+}
+
+pub struct AutomationEngine {
+    event: &'static str,
+    status: Mutex<AutomationStatus>,
+    replay_status: Mutex<AutomationStatus>,
+    session: Mutex<Session>,
+    last_pregame: Mutex<Option<PregameState>>,
+}
+
+struct Context {
+    tokens: AuthTokens,
+    puuid: String,
+    region: String,
+    shard: String,
+}
+
+fn taken_agents(pregame: &PregameState) -> HashSet<String> {
+    pregame.allies.iter()
+        .filter(|p| !p.is_self && p.selection_state == "locked")
+        .filter_map(|p| p.character_id.as_ref().map(|id| id.to_lowercase()))
+        .collect()
+}
+
+fn pick_agent(priority: &[String], owned: &HashSet<String>, pregame: &PregameState, skip: &HashSet<String>) -> Option<String> {
+    let taken = taken_agents(pregame);
+    priority.iter()
+        .map(|id| id.to_lowercase())
+        .find(|id| owned.contains(id) && !taken.contains(id) && !skip.contains(id))
+}
+
+fn audit_entry(feature: &str, action: &str, pregame: &PregameState) -> AuditEntry {
+    AuditEntry {
+        timestamp: crate::store::now_ms(),
+        feature: feature.to_string(),
+        action: action.to_string(),
+        match_id: pregame.match_id.clone(),
+        map_name: pregame.map_name.clone(),
+        queue_id: pregame.queue_id.clone(),
+        phase_remaining_ms: pregame.phase_time_remaining_ms,
+        ..Default::default()
+    }
+}
+
+fn record(cfg: &AppConfig, entry: AuditEntry) {
+    if let Err(e) = audit::append(entry, &cfg.audit) {
+        println!("[audit] failed to write entry: {}", e);
+    }
+}
+
+fn note(cfg: &AppConfig, session: &mut Session, entry: AuditEntry) {
+    let key = format!("{}:{}:{}", entry.feature, entry.action, entry.message);
+    if session.logged.insert(key) {
+        record(cfg, entry);
+    }
+}
+
+fn dry_run_message(action: PregameAction<'_>, entry: &AuditEntry) -> String {
+    let what = match action {
+        PregameAction::Select(agent_id) => format!("selected {}", agent_id),
+        PregameAction::Lock(agent_id) => format!("locked {}", agent_id),
+        PregameAction::Quit => "dodged".to_string(),
+    };
+    if entry.rule.is_empty() {
+        format!("Would have {} on {}", what, entry.map_name)
+    } else {
+        format!("Would have {} on {} ({})", what, entry.map_name, entry.rule)
+    }
+}
+
+async fn perform(app: &AppHandle, cfg: &AppConfig, ctx: &Context, action: PregameAction<'_>, delay: Duration, entry: AuditEntry) -> Result<(), String> {
+    if cfg.automation.dry_run {
+        let entry = AuditEntry {
+            action: format!("dry-{}", action.name()),
+            success: true,
+            message: dry_run_message(action, &entry),
+            delay_ms: delay.as_millis() as u64,
+            ..entry
+        };
+        println!("[automation] dry run: {}", entry.message);
+        let _ = app.emit("automation-dry-run", &entry);
+        record(cfg, entry);
+        return Ok(());
+    }
+    if !delay.is_zero() {
+        tokio::time::sleep(delay).await;
+    }
+    let started = Instant::now();
+    let sent = api::pregame_action(&ctx.tokens, &ctx.region, &ctx.shard, &entry.match_id, action).await;
+    let duration_ms = started.elapsed().as_millis() as u64;
+    let (http_status, result) = match sent {
+        Ok(status) => (Some(status), api::action_result(action, status)),
+        Err(e) => (None, Err(e)),
+    };
+    record(cfg, AuditEntry {
+        http_status,
+        success: result.is_ok(),
+        message: result.as_ref().err().cloned().unwrap_or_default(),
+        delay_ms: delay.as_millis() as u64,
+        duration_ms,
+        ..entry
+    });
+    result
+}
+
+impl AutomationEngine {
+    pub fn new() -> Self {
+        Self::with_event("automation-status")
+    }
+
+    fn with_event(event: &'static str) -> Self {
+        Self {
+            event,
+            status: Mutex::new(AutomationStatus::default()),
+            replay_status: Mutex::new(AutomationStatus::default()),
+            session: Mutex::new(Session::default()),
+            last_pregame: Mutex::new(None),
+        }
+    }
+
+    pub async fn status(&self) -> AutomationStatus {
+        self.status.lock().await.clone()
+    }
+
+    pub async fn replay_status(&self) -> AutomationStatus {
+        self.replay_status.lock().await.clone()
+    }
+
+    async fn update(&self, app: &AppHandle, apply: impl FnOnce(&mut AutomationStatus)) {
+        let mut status = self.status.lock().await;
+        let mut next = status.clone();
+        apply(&mut next);
+        if *status == next {
+            return;
+        }
+        *status = next;
+        let _ = app.emit(self.event, &*status);
+    }
+
+    async fn set_instalock(&self, app: &AppHandle, instalock: InstalockStatus) {
+        self.update(app, |s| s.instalock = instalock).await;
+    }
+
+    async fn set_dodge(&self, app: &AppHandle, dodge: DodgeStatus) {
+        self.update(app, |s| s.dodge = dodge).await;
+    }
+
+    async fn context(conn: &ValorantConnection) -> Option<Context> {
+        let state = conn.get_state().await;
+        if state.status != ConnectionStatus::Connected {
+            return None;
+        }
+        Some(Context {
+            puuid: state.player_info?.puuid,
+            region: state.region?,
+            shard: state.shard?,
+            tokens: conn.get_tokens().await?,
+        })
+    }
+
+    pub async fn tick(&self, app: &AppHandle, conn: &ValorantConnection) {
+        let cfg = config::load();
+        if !cfg.instalock.active && !cfg.map_dodge.active {
+            self.update(app, |s| *s = AutomationStatus::default()).await;
+            return;
+        }
+
+        let Some(ctx) = Self::context(conn).await else {
+            self.update(app, |s| *s = AutomationStatus::default()).await;
+            return;
+        };
+
+        let Some(pregame) = api::fetch_pregame(&ctx.tokens, &ctx.puuid, &ctx.region, &ctx.shard).await else {
+            *self.session.lock().await = Session::default();
+            self.update(app, |s| {
+                s.instalock = InstalockStatus {
+                    state: if cfg.instalock.active { InstalockState::Waiting } else { InstalockState::Idle },
+                    ..Default::default()
+                };
+                s.dodge = DodgeStatus {
+                    state: if cfg.map_dodge.active { DodgeState::Waiting } else { DodgeState::Idle },
+                    ..Default::default()
+                };
+                s.dry_run = cfg.automation.dry_run;
+            }).await;
+            return;
+        };
+        *self.last_pregame.lock().await = Some(pregame.clone());
+
+        let mut session = self.session.lock().await;
+        if session.match_id != pregame.match_id {
+            *session = Session { match_id: pregame.match_id.clone(), ..Default::default() };
+        }
+        self.run(app, &cfg, &ctx, &pregame, &mut session).await;
+    }
+
+    pub async fn replay(&self, app: &AppHandle, conn: &ValorantConnection, pregame: Option<PregameState>) -> Result<AutomationStatus, String> {
+        let mut cfg = config::load();
+        cfg.automation.dry_run = true;
+        let ctx = Self::context(conn).await.ok_or("Not connected to Valorant")?;
+        let pregame = match pregame {
+            Some(p) => p,
+            None => self.last_pregame.lock().await.clone().ok_or("No pregame lobby seen yet to replay")?,
+        };
+        println!("[automation] replaying pregame {} on {}", pregame.match_id, pregame.map_name);
+
+        let replay = Self::with_event("automation-replay");
+        let mut session = Session { match_id: pregame.match_id.clone(), ..Default::default() };
+        let mut remaining_ms = pregame.phase_time_remaining_ms;
+        loop {
+            let tick = PregameState { phase_time_remaining_ms: remaining_ms, ..pregame.clone() };
+            replay.run(app, &cfg, &ctx, &tick, &mut session).await;
+            if session.done || session.dodged || remaining_ms == 0 {
+                break;
+            }
+            remaining_ms = remaining_ms.saturating_sub(TICK.as_millis() as u64);
+        }
+
+        let status = replay.status().await;
+        *self.replay_status.lock().await = status.clone();
+        Ok(status)
+    }
+
+    async fn run(&self, app: &AppHandle, cfg: &AppConfig, ctx: &Context, pregame: &PregameState, session: &mut Session) {
+        self.update(app, |s| s.dry_run = cfg.automation.dry_run).await;
+        if self.run_dodge(app, cfg, ctx, pregame, session).await {
+            return;
+        }
+        self.run_instalock(app, cfg, ctx, pregame, session).await;
+    }
+
+    async fn ensure_agents(ctx: &Context, session: &mut Session) -> bool {
+        if session.agents.is_none() {
+            let agents = api::fetch_agents(&ctx.tokens, &ctx.puuid, &ctx.shard).await;
+            if agents.is_empty() {
+                return false;
+            }
+            session.agents = Some(agents);
+        }
+        true
+    }
+
+    async fn run_dodge(&self, app: &AppHandle, cfg: &AppConfig, ctx: &Context, pregame: &PregameState, session: &mut Session) -> bool {
+        let base = DodgeStatus {
+            match_id: pregame.match_id.clone(),
+            map_name: pregame.map_name.clone(),
+            queue_id: pregame.queue_id.clone(),
+            ..Default::default()
+        };
+
+        if session.dodged {
+            return true;
+        }
+        if !cfg.map_dodge.active {
+            self.set_dodge(app, DodgeStatus::default()).await;
+            return false;
+        }
+        if !cfg.dodge_enabled_for(&pregame.queue_id) {
+            let message = format!("Map dodge is off for {}", queue_label(&pregame.queue_id));
+            note(cfg, session, AuditEntry { message: message.clone(), ..audit_entry("dodge", "skip", pregame) });
+            self.set_dodge(app, DodgeStatus { state: DodgeState::Disabled, message, ..base }).await;
+            return false;
+        }
+        Self::ensure_agents(ctx, session).await;
+        let uses_watchlist = cfg.map_dodge.rules.iter()
+            .any(|r| r.enabled && r.conditions.iter().any(|c| matches!(c, DodgeCondition::Watchlisted { .. })));
+        let watchlist = if uses_watchlist { watchlist::all() } else { Vec::new() };
+        let input = RuleInput {
+            pregame,
+            agents: session.agents.as_deref().unwrap_or_default(),
+            watchlist: &watchlist,
+        };
+
+        let Some(hit) = rules::evaluate(&cfg.map_dodge, &input) else {
+            self.set_dodge(app, DodgeStatus { state: DodgeState::Safe, ..base }).await;
+            return false;
+        };
+
+        let reason = format!("{} — {}", hit.rule, hit.reasons.join(", "));
+        if session.fired.insert(hit.rule_id.clone()) {
+            let mode = if cfg.map_dodge.test_mode { " (test mode)" } else { "" };
+            println!("[dodge] rule \"{}\" fired in {}{}: {}", hit.rule, pregame.match_id, mode, hit.reasons.join(", "));
+        }
+
+        let entry = AuditEntry { rule: hit.rule.clone(), ..audit_entry("dodge", "quit", pregame) };
+        if cfg.map_dodge.test_mode {
+            let message = format!("Would dodge: {}", reason);
+            note(cfg, session, AuditEntry { action: "test".to_string(), success: true, message: message.clone(), ..entry });
+            self.set_dodge(app, DodgeStatus { state: DodgeState::Test, rule: Some(hit), message, ..base }).await;
+            return false;
+        }
+
+        if let Some(message) = dodges::cap_reached(&ctx.puuid, &cfg.map_dodge) {
+            note(cfg, session, AuditEntry { action: "capped".to_string(), message: message.clone(), ..entry });
+            self.set_dodge(app, DodgeStatus { state: DodgeState::Capped, rule: Some(hit), message, ..base }).await;
+            return false;
+        }
+
+        let rule = hit.rule.clone();
+        let base = DodgeStatus { rule: Some(hit), message: reason, ..base };
+        self.set_dodge(app, DodgeStatus { state: DodgeState::Dodging, ..base.clone() }).await;
+        match perform(app, cfg, ctx, PregameAction::Quit, Duration::ZERO, entry).await {
+            Ok(()) => {
+                session.dodged = true;
+                if !cfg.automation.dry_run {
+                    let _ = dodges::record(&ctx.puuid, dodges::DodgeRecord {
+                        match_id: pregame.match_id.clone(),
+                        timestamp: crate::store::now_ms(),
+                        map_name: pregame.map_name.clone(),
+                        queue_id: pregame.queue_id.clone(),
+                        automatic: true,
+                        rule,
+                    });
+                }
+                self.set_dodge(app, DodgeStatus { state: DodgeState::Dodged, ..base }).await;
+            }
+            Err(e) => {
+                self.set_dodge(app, DodgeStatus { state: DodgeState::Failed, message: e, ..base }).await;
+            }
+        }
+        true
+    }
+
+    async fn keep_hover(&self, app: &AppHandle, cfg: &AppConfig, ctx: &Context, pregame: &PregameState, session: &mut Session, base: &InstalockStatus) -> bool {
+        let Some(hovered) = session.hovered.clone() else {
+            return false;
+        };
+        if taken_agents(pregame).contains(&hovered) {
+            session.failed.insert(hovered);
+            session.hovered = None;
+            session.simulated_hover = false;
+            return false;
+        }
+
+        let still_hovered = session.simulated_hover
+            || pregame.selected_agent.as_ref().is_some_and(|id| id.eq_ignore_ascii_case(&hovered));
+        if !still_hovered {
+            let message = "You switched agents — leaving your pick alone".to_string();
+            note(cfg, session, AuditEntry { agent_id: Some(hovered.clone()), message: message.clone(), ..audit_entry("instalock", "skip", pregame) });
+            self.set_instalock(app, InstalockStatus { state: InstalockState::Hovering, agent_id: Some(hovered), message, ..base.clone() }).await;
+            return true;
+        }
+
+        let deadline_ms = cfg.instalock.auto_lock_secs as u64 * 1000;
+        let due = pregame.phase_time_remaining_ms > 0 && pregame.phase_time_remaining_ms <= deadline_ms;
+        if !cfg.instalock.auto_lock || !due {
+            self.set_instalock(app, InstalockStatus { state: InstalockState::Hovering, agent_id: Some(hovered), message: hover_message(cfg), ..base.clone() }).await;
+            return true;
+        }
+
+        self.set_instalock(app, InstalockStatus { state: InstalockState::Locking, agent_id: Some(hovered.clone()), ..base.clone() }).await;
+        let entry = AuditEntry { agent_id: Some(hovered.clone()), rule: "Auto-lock".to_string(), ..audit_entry("instalock", "lock", pregame) };
+        match perform(app, cfg, ctx, PregameAction::Lock(&hovered), Duration::ZERO, entry).await {
+            Ok(()) => {
+                session.done = true;
+                self.set_instalock(app, InstalockStatus { state: InstalockState::Locked, agent_id: Some(hovered), ..base.clone() }).await;
+            }
+            Err(e) => {
+                self.set_instalock(app, InstalockStatus { state: InstalockState::Hovering, agent_id: Some(hovered), message: e, ..base.clone() }).await;
+            }
+        }
+        true
+    }
+
+    async fn run_instalock(&self, app: &AppHandle, cfg: &AppConfig, ctx: &Context, pregame: &PregameState, session: &mut Session) {
+        let base = InstalockStatus {
+            match_id: pregame.match_id.clone(),
+            map_name: pregame.map_name.clone(),
+            queue_id: pregame.queue_id.clone(),
+            ..Default::default()
+        };
+
+        if !cfg.instalock.active {
+            self.set_instalock(app, InstalockStatus::default()).await;
+            return;
+        }
+
+        if pregame.locked {
+            session.done = true;
+            self.set_instalock(app, InstalockStatus { state: InstalockState::Locked, agent_id: pregame.locked_agent.clone(), ..base }).await;
+            return;
+        }
+        if session.done {
+            return;
+        }
+
+        if !cfg.instalock_enabled_for(&pregame.queue_id) {
+            let message = format!("Instalock is off for {}", queue_label(&pregame.queue_id));
+            note(cfg, session, AuditEntry { message: message.clone(), ..audit_entry("instalock", "skip", pregame) });
+            self.set_instalock(app, InstalockStatus { state: InstalockState::Disabled, message, ..base }).await;
+            return;
+        }
+
+        if !Self::ensure_agents(ctx, session).await {
+            return;
+        }
+        let owned: HashSet<String> = session.agents.iter().flatten()
+            .filter(|a| a.unlocked)
+            .map(|a| a.uuid.to_lowercase())
+            .collect();
+
+        let priority = cfg.priority_for(&pregame.queue_id, &pregame.map_name);
+        if priority.is_empty() {
+            self.set_instalock(app, InstalockStatus { state: InstalockState::Failed, message: "No agents in your priority list".to_string(), ..base }).await;
+            return;
+        }
+
+        if cfg.instalock.hover_only && self.keep_hover(app, cfg, ctx, pregame, session, &base).await {
+            return;
+        }
+
+        let Some(agent_id) = pick_agent(&priority, &owned, pregame, &session.failed) else {
+            let message = "Every agent in your priority list is taken or not owned".to_string();
+            note(cfg, session, AuditEntry { message: message.clone(), ..audit_entry("instalock", "skip", pregame) });
+            self.set_instalock(app, InstalockStatus { state: InstalockState::Failed, message, ..base }).await;
+            return;
+        };
+        let entry = AuditEntry { agent_id: Some(agent_id.clone()), ..audit_entry("instalock", "select", pregame) };
+
+        if cfg.instalock.hover_only {
+            self.set_instalock(app, InstalockStatus { state: InstalockState::Hovering, agent_id: Some(agent_id.clone()), message: "Selecting...".to_string(), ..base.clone() }).await;
+            let delay = timing::select_delay(&cfg.timing);
+            let entry = AuditEntry { rule: "Hover".to_string(), ..entry };
+            match perform(app, cfg, ctx, PregameAction::Select(&agent_id), delay, entry).await {
+                Ok(()) => {
+                    session.hovered = Some(agent_id.clone());
+                    session.simulated_hover = cfg.automation.dry_run;
+                    self.set_instalock(app, InstalockStatus { state: InstalockState::Hovering, agent_id: Some(agent_id), message: hover_message(cfg), ..base }).await;
+                }
+                Err(e) => {
+                    session.failed.insert(agent_id.clone());
+                    self.set_instalock(app, InstalockStatus { state: InstalockState::Hovering, agent_id: Some(agent_id), message: e, ..base }).await;
+                }
+            }
+            return;
+        }
+
+        let (select_delay, lock_delay) = match timing::schedule(&cfg.timing, pregame.phase_time_remaining_ms) {
+            Schedule::Wait { remaining_ms } => {
+                let message = format!("Locking with {}s left ({}s remaining)", cfg.timing.deadline_secs, remaining_ms / 1000);
+                self.set_instalock(app, InstalockStatus { state: InstalockState::Locking, agent_id: Some(agent_id), message, ..base }).await;
+                return;
+            }
+            Schedule::Run { select_delay, lock_delay } => (select_delay, lock_delay),
+        };
+
+        self.set_instalock(app, InstalockStatus { state: InstalockState::Locking, agent_id: Some(agent_id.clone()), ..base.clone() }).await;
+
+        perform(app, cfg, ctx, PregameAction::Select(&agent_id), select_delay, entry.clone()).await.ok();
+        let entry = AuditEntry { action: "lock".to_string(), ..entry };
+        match perform(app, cfg, ctx, PregameAction::Lock(&agent_id), lock_delay, entry).await {
+            Ok(()) => {
+                session.done = true;
+                self.set_instalock(app, InstalockStatus { state: InstalockState::Locked, agent_id: Some(agent_id), ..base }).await;
+            }
+            Err(e) => {
+                session.failed.insert(agent_id.clone());
+                self.set_instalock(app, InstalockStatus { state: InstalockState::Locking, agent_id: Some(agent_id), message: e, ..base }).await;
+            }
+        }
+    }
+}
+
+pub async fn manual_dodge(conn: &ValorantConnection, match_id: Option<&str>) -> Result<String, String> {
+    let ctx = AutomationEngine::context(conn).await.ok_or("Not connected to Valorant")?;
+    let pregame = api::fetch_pregame(&ctx.tokens, &ctx.puuid, &ctx.region, &ctx.shard).await
+        .filter(|p| match_id.is_none_or(|id| p.match_id == id));
+    let match_id = match match_id {
+        Some(id) => id.to_string(),
+        None => pregame.as_ref().map(|p| p.match_id.clone()).ok_or("Not in agent select")?,
+    };
+    api::quit_pregame(&ctx.tokens, &ctx.region, &ctx.shard, &match_id).await?;
+    let map_name = pregame.as_ref().map(|p| p.map_name.clone()).unwrap_or_default();
+    let _ = dodges::record(&ctx.puuid, dodges::DodgeRecord {
+        match_id,
+        timestamp: crate::store::now_ms(),
+        map_name: map_name.clone(),
+        queue_id: pregame.map(|p| p.queue_id).unwrap_or_default(),
+        automatic: false,
+        rule: "Manual".to_string(),
+    });
+    Ok(map_name)
+}
+
+fn hover_message(cfg: &AppConfig) -> String {
+    if cfg.instalock.auto_lock {
+        format!("Hovering — auto-locks with {}s left", cfg.instalock.auto_lock_secs)
+    } else {
+        "Hovering — lock in yourself when ready".to_string()
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use serde::Serialize;
+use crate::card::rank_name;
+use crate::config::{self, DodgeCondition, DodgeRule, MapDodgeConfig};
+use crate::store::watchlist::WatchEntry;
+use crate::valorant::score::queue_label;
+use crate::valorant::types::{AgentInfo, PregameState};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleMatch {
+    pub rule_id: String,
+    pub rule: String,
+    pub reasons: Vec<String>,
+}
+
+pub struct RuleInput<'a> {
+    pub pregame: &'a PregameState,
+    pub agents: &'a [AgentInfo],
+    pub watchlist: &'a [WatchEntry],
+}
+
+fn agent<'a>(input: &'a RuleInput, id: &str) -> Option<&'a AgentInfo> {
+    input.agents.iter().find(|a| a.uuid.eq_ignore_ascii_case(id))
+}
+
+fn matches_any(list: &[String], value: &str) -> bool {
+    list.iter().any(|v| v.eq_ignore_ascii_case(value))
+}
+
+fn check(condition: &DodgeCondition, input: &RuleInput) -> Option<String> {
+    let pregame = input.pregame;
+    match condition {
+        DodgeCondition::Map { maps } => {
+            matches_any(maps, &pregame.map_name).then(|| format!("map is {}", pregame.map_name))
+        }
+        DodgeCondition::Queue { queues } => {
+            let key = config::queue_key(&pregame.queue_id);
+            matches_any(queues, key).then(|| format!("queue is {}", queue_label(&pregame.queue_id)))
+        }
+        DodgeCondition::Watchlisted { tags } => {
+            let hits: Vec<String> = pregame.allies.iter()
+                .filter(|p| !p.is_self)
+                .filter_map(|p| input.watchlist.iter().find(|w| w.puuid == p.puuid))
+                .filter(|w| tags.is_empty() || w.tags.iter().any(|t| matches_any(tags, t)))
+                .map(|w| if w.game_name.is_empty() { w.puuid.chars().take(8).collect::<String>() } else { format!("{}#{}", w.game_name, w.tag_line) })
+                .collect();
+            (!hits.is_empty()).then(|| format!("watchlisted teammate {}", hits.join(", ")))
+        }
+        DodgeCondition::DuplicateRole { roles } => {
+            let mut by_role: HashMap<&str, Vec<&str>> = HashMap::new();
+            for player in pregame.allies.iter().filter(|p| p.selection_state == "locked") {
+                let Some(info) = player.character_id.as_deref().and_then(|id| agent(input, id)) else {
+                    continue;
+                };
+                by_role.entry(info.role.as_str()).or_default().push(info.name.as_str());
+            }
+            let mut dupes: Vec<String> = by_role.into_iter()
+                .filter(|(role, names)| names.len() > 1 && (roles.is_empty() || matches_any(roles, role)))
+                .map(|(role, names)| format!("{} {}s locked ({})", names.len(), role.to_lowercase(), names.join(", ")))
+                .collect();
+            dupes.sort();
+            (!dupes.is_empty()).then(|| dupes.join("; "))
+        }
+        DodgeCondition::AllyRankBelow { tier } => {
+            let ranked: Vec<u32> = pregame.allies.iter()
+                .filter(|p| !p.is_self && p.competitive_tier > 0)
+                .map(|p| p.competitive_tier)
+                .collect();
+            if ranked.is_empty() {
+                return None;
+            }
+            let avg = ranked.iter().sum::<u32>() as f32 / ranked.len() as f32;
+            (avg < *tier as f32).then(|| format!(
+                "average ally rank {} is below {}",
+                rank_name(avg.round() as u32),
+                rank_name(*tier),
+            ))
+        }
+    }
+}
+
+fn check_rule(rule: &DodgeRule, input: &RuleInput) -> Option<RuleMatch> {
+    if !rule.enabled || rule.conditions.is_empty() {
+        return None;
+    }
+    let reasons = rule.conditions.iter()
+        .map(|c| check(c, input))
+        .collect::<Option<Vec<String>>>()?;
+    Some(RuleMatch {
+        rule_id: rule.id.clone(),
+        rule: if rule.name.is_empty() { "Unnamed rule".to_string() } else { rule.name.clone() },
+        reasons,
+    })
+}
+
+pub fn evaluate(cfg: &MapDodgeConfig, input: &RuleInput) -> Option<RuleMatch> {
+    if matches_any(&cfg.blacklisted_maps, &input.pregame.map_name) {
+        return Some(RuleMatch {
+            rule_id: "blacklist".to_string(),
+            rule: "Map blacklist".to_string(),
+            reasons: vec![format!("{} is blacklisted", input.pregame.map_name)],
+        });
+    }
+    cfg.rules.iter().find_map(|rule| check_rule(rule, input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::valorant::types::PregamePlayer;
+
+    fn rule(id: &str, conditions: Vec<DodgeCondition>) -> DodgeRule {
+        DodgeRule { id: id.to_string(), name: id.to_string(), enabled: true, conditions }
+    }
+
+    fn ally(puuid: &str, agent: Option<&str>, tier: u32) -> PregamePlayer {
+        PregamePlayer {
+            puuid: puuid.to_string(),
+            character_id: agent.map(str::to_string),
+            selection_state: if agent.is_some() { "locked".to_string() } else { String::new() },
+            competitive_tier: tier,
+            ..Default::default()
+        }
+    }
+
+    fn agent_info(uuid: &str, name: &str, role: &str) -> AgentInfo {
+        AgentInfo {
+            uuid: uuid.to_string(),
+            name: name.to_string(),
+            icon: String::new(),
+            role: role.to_string(),
+            role_icon: String::new(),
+            unlocked: true,
+        }
+    }
+
+    fn pregame(map_name: &str) -> PregameState {
+        PregameState { map_name: map_name.to_string(), queue_id: "competitive".to_string(), ..Default::default() }
+    }
+
+    fn run(cfg: &MapDodgeConfig, pregame: &PregameState, agents: &[AgentInfo], watchlist: &[WatchEntry]) -> Option<RuleMatch> {
+        evaluate(cfg, &RuleInput { pregame, agents, watchlist })
+    }
+
+    fn map_rule(id: &str, map: &str) -> DodgeRule {
+        rule(id, vec![DodgeCondition::Map { maps: vec![map.to_string()] }])
+    }
+
+    #[test]
+    fn blacklist_is_checked_before_rules() {
+        let cfg = MapDodgeConfig {
+            blacklisted_maps: vec!["bind".to_string()],
+            rules: vec![map_rule("bind-rule", "Bind")],
+            ..Default::default()
+        };
+        let hit = run(&cfg, &pregame("Bind"), &[], &[]).unwrap();
+        assert_eq!(hit.rule_id, "blacklist");
+        assert_eq!(hit.reasons, vec!["Bind is blacklisted".to_string()]);
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let cfg = MapDodgeConfig {
+            rules: vec![map_rule("haven", "Haven"), map_rule("bind-a", "Bind"), map_rule("bind-b", "Bind")],
+            ..Default::default()
+        };
+        assert_eq!(run(&cfg, &pregame("Bind"), &[], &[]).unwrap().rule_id, "bind-a");
+        assert!(run(&cfg, &pregame("Ascent"), &[], &[]).is_none());
+    }
+
+    #[test]
+    fn disabled_and_empty_rules_never_match() {
+        let mut disabled = map_rule("disabled", "Bind");
+        disabled.enabled = false;
+        let cfg = MapDodgeConfig { rules: vec![disabled, rule("empty", Vec::new())], ..Default::default() };
+        assert!(run(&cfg, &pregame("Bind"), &[], &[]).is_none());
+    }
+
+    #[test]
+    fn every_condition_must_match() {
+        let both = rule("both", vec![
+            DodgeCondition::Map { maps: vec!["Bind".to_string()] },
+            DodgeCondition::Queue { queues: vec!["unrated".to_string()] },
+        ]);
+        let cfg = MapDodgeConfig { rules: vec![both], ..Default::default() };
+        assert!(run(&cfg, &pregame("Bind"), &[], &[]).is_none());
+    }
+
+    #[test]
+    fn duplicate_role_counts_only_locked_allies() {
+        let agents = [agent_info("a", "Jett", "Duelist"), agent_info("b", "Reyna", "Duelist"), agent_info("c", "Sage", "Sentinel")];
+        let mut state = pregame("Bind");
+        state.allies = vec![ally("1", Some("a"), 0), ally("2", Some("c"), 0), ally("3", None, 0)];
+        let cfg = MapDodgeConfig {
+            rules: vec![rule("dupes", vec![DodgeCondition::DuplicateRole { roles: Vec::new() }])],
+            ..Default::default()
+        };
+        assert!(run(&cfg, &state, &agents, &[]).is_none());
+
+        state.allies.push(ally("4", Some("B"), 0));
+        let hit = run(&cfg, &state, &agents, &[]).unwrap();
+        assert_eq!(hit.reasons, vec!["2 duelists locked (Jett, Reyna)".to_string()]);
+    }
+
+    #[test]
+    fn ally_rank_ignores_self_and_unranked() {
+        let mut state = pregame("Bind");
+        let mut me = ally("me", None, 3);
+        me.is_self = true;
+        state.allies = vec![me, ally("1", None, 12), ally("2", None, 0), ally("3", None, 14)];
+        let below = |tier| MapDodgeConfig {
+            rules: vec![rule("rank", vec![DodgeCondition::AllyRankBelow { tier }])],
+            ..Default::default()
+        };
+        assert!(run(&below(13), &state, &[], &[]).is_none());
+        assert!(run(&below(14), &state, &[], &[]).is_some());
+    }
+
+    #[test]
+    fn watchlisted_teammate_without_name_uses_short_puuid() {
+        let mut state = pregame("Bind");
+        state.allies = vec![ally("ééééééééé-rest", None, 0)];
+        let watchlist = [WatchEntry {
+            puuid: "ééééééééé-rest".to_string(),
+            game_name: String::new(),
+            tag_line: String::new(),
+            tags: vec!["toxic".to_string()],
+            note: String::new(),
+            added_at: 0,
+        }];
+        let cfg = MapDodgeConfig {
+            rules: vec![rule("watch", vec![DodgeCondition::Watchlisted { tags: vec!["Toxic".to_string()] }])],
+            ..Default::default()
+        };
+        let hit = run(&cfg, &state, &[], &watchlist).unwrap();
+        assert_eq!(hit.reasons, vec!["watchlisted teammate éééééééé".to_string()]);
+    }
+}
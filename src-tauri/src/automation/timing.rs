@@ -0,0 +1,157 @@
+use std::time::Duration;
+use rand::Rng;
+use rand_distr::{Distribution, Normal};
+use crate::config::{DelayDistribution, TimingConfig};
+
+const TICK_MARGIN_MS: u64 = 1000;
+
+const HUMANIZED_SELECT: DelayDistribution = DelayDistribution::Normal { mean: 700, std_dev: 200, min: 350, max: 1400 };
+const HUMANIZED_LOCK: DelayDistribution = DelayDistribution::Normal { mean: 450, std_dev: 150, min: 200, max: 900 };
+const HUMANIZED_MIN_GAP: u32 = 150;
+
+pub enum Schedule {
+    Wait { remaining_ms: u64 },
+    Run { select_delay: Duration, lock_delay: Duration },
+}
+
+fn sample(dist: &DelayDistribution) -> u64 {
+    let mut rng = rand::thread_rng();
+    let ms = match *dist {
+        DelayDistribution::Fixed { ms } => ms,
+        DelayDistribution::Uniform { min, max } => {
+            if max > min { rng.gen_range(min..=max) } else { min }
+        }
+        DelayDistribution::Normal { mean, std_dev, min, max } => {
+            let (lo, hi) = (min.min(max) as f64, min.max(max) as f64);
+            Normal::new(mean as f64, std_dev as f64)
+                .map(|n| n.sample(&mut rng))
+                .unwrap_or(mean as f64)
+                .clamp(lo, hi) as u32
+        }
+    };
+    ms as u64
+}
+
+fn delays(timing: &TimingConfig) -> (u64, u64) {
+    match timing.preset.as_str() {
+        "humanized" => (sample(&HUMANIZED_SELECT), sample(&HUMANIZED_LOCK).max(HUMANIZED_MIN_GAP as u64)),
+        "custom" => (sample(&timing.select), sample(&timing.lock).max(timing.min_gap as u64)),
+        "deadline" => (0, timing.min_gap as u64),
+        _ => (0, 0),
+    }
+}
+
+pub fn select_delay(timing: &TimingConfig) -> Duration {
+    Duration::from_millis(delays(timing).0)
+}
+
+pub fn schedule(timing: &TimingConfig, phase_remaining_ms: u64) -> Schedule {
+    let (select_delay, lock_delay) = delays(timing);
+
+    if timing.preset == "deadline" && phase_remaining_ms > 0 {
+        let target = timing.deadline_secs as u64 * 1000 + lock_delay;
+        let wait = phase_remaining_ms.saturating_sub(target);
+        if wait > TICK_MARGIN_MS {
+            return Schedule::Wait { remaining_ms: phase_remaining_ms };
+        }
+        return Schedule::Run {
+            select_delay: Duration::from_millis(wait),
+            lock_delay: Duration::from_millis(lock_delay),
+        };
+    }
+
+    Schedule::Run {
+        select_delay: Duration::from_millis(select_delay),
+        lock_delay: Duration::from_millis(lock_delay),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(preset: &str, select: DelayDistribution, lock: DelayDistribution, min_gap: u32) -> TimingConfig {
+        let mut cfg = TimingConfig::default();
+        cfg.preset = preset.to_string();
+        cfg.select = select;
+        cfg.lock = lock;
+        cfg.min_gap = min_gap;
+        cfg
+    }
+
+    fn deadline(min_gap: u32) -> TimingConfig {
+        timing("deadline", DelayDistribution::default(), DelayDistribution::default(), min_gap)
+    }
+
+    fn run_delays(schedule: Schedule) -> (u64, u64) {
+        match schedule {
+            Schedule::Run { select_delay, lock_delay } => (select_delay.as_millis() as u64, lock_delay.as_millis() as u64),
+            Schedule::Wait { remaining_ms } => panic!("expected to run, waiting with {}ms left", remaining_ms),
+        }
+    }
+
+    #[test]
+    fn instant_runs_immediately() {
+        let cfg = TimingConfig::default();
+        assert_eq!(run_delays(schedule(&cfg, 30_000)), (0, 0));
+    }
+
+    #[test]
+    fn custom_lock_respects_min_gap() {
+        let cfg = timing("custom", DelayDistribution::Fixed { ms: 300 }, DelayDistribution::Fixed { ms: 50 }, 200);
+        assert_eq!(run_delays(schedule(&cfg, 30_000)), (300, 200));
+    }
+
+    #[test]
+    fn deadline_waits_outside_tick_margin() {
+        let cfg = deadline(200);
+        let target = 5_000 + 200;
+        match schedule(&cfg, target + TICK_MARGIN_MS + 1) {
+            Schedule::Wait { remaining_ms } => assert_eq!(remaining_ms, target + TICK_MARGIN_MS + 1),
+            Schedule::Run { .. } => panic!("ran outside the tick margin"),
+        }
+    }
+
+    #[test]
+    fn deadline_sleeps_off_the_margin_inside_it() {
+        let cfg = deadline(200);
+        let target = 5_000 + 200;
+        assert_eq!(run_delays(schedule(&cfg, target + TICK_MARGIN_MS)), (TICK_MARGIN_MS, 200));
+        assert_eq!(run_delays(schedule(&cfg, target + 1)), (1, 200));
+    }
+
+    #[test]
+    fn deadline_runs_at_once_when_already_late() {
+        let cfg = deadline(200);
+        assert_eq!(run_delays(schedule(&cfg, 3_000)), (0, 200));
+    }
+
+    #[test]
+    fn deadline_without_phase_timer_runs_immediately() {
+        assert_eq!(run_delays(schedule(&deadline(150), 0)), (0, 150));
+    }
+
+    #[test]
+    fn uniform_with_inverted_bounds_uses_min() {
+        assert_eq!(sample(&DelayDistribution::Uniform { min: 400, max: 100 }), 400);
+    }
+
+    #[test]
+    fn normal_samples_stay_within_bounds() {
+        let dist = DelayDistribution::Normal { mean: 500, std_dev: 5_000, min: 900, max: 100 };
+        for _ in 0..200 {
+            let ms = sample(&dist);
+            assert!((100..=900).contains(&ms), "{} out of bounds", ms);
+        }
+    }
+
+    #[test]
+    fn humanized_delays_stay_within_presets() {
+        let cfg = timing("humanized", DelayDistribution::default(), DelayDistribution::default(), 0);
+        for _ in 0..200 {
+            let (select, lock) = run_delays(schedule(&cfg, 30_000));
+            assert!((350..=1400).contains(&select));
+            assert!((200..=900).contains(&lock));
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use std::path::PathBuf;
+use ab_glyph::{point, Font, FontRef, PxScale, ScaleFont};
+use image::{imageops, Rgba, RgbaImage};
+use crate::assets;
+use crate::valorant::types::CompUpdate;
+
+const FONT: &[u8] = include_bytes!("../../ui/assets/fonts/Montserrat.ttf");
+
+const CARD_WIDTH: u32 = 800;
+const CARD_HEIGHT: u32 = 320;
+
+const RANK_NAMES: [&str; 28] = [
+    "Unranked", "Unused 1", "Unused 2",
+    "Iron 1", "Iron 2", "Iron 3",
+    "Bronze 1", "Bronze 2", "Bronze 3",
+    "Silver 1", "Silver 2", "Silver 3",
+    "Gold 1", "Gold 2", "Gold 3",
+    "Platinum 1", "Platinum 2", "Platinum 3",
+    "Diamond 1", "Diamond 2", "Diamond 3",
+    "Ascendant 1", "Ascendant 2", "Ascendant 3",
+    "Immortal 1", "Immortal 2", "Immortal 3",
+    "Radiant",
+];
+
+const WHITE: Rgba<u8> = Rgba([255, 255, 255, 255]);
+const MUTED: Rgba<u8> = Rgba([170, 170, 180, 255]);
+const WIN: Rgba<u8> = Rgba([64, 192, 96, 255]);
+const LOSS: Rgba<u8> = Rgba([224, 64, 80, 255]);
+
+pub fn rank_name(tier: u32) -> &'static str {
+    RANK_NAMES.get(tier as usize).copied().unwrap_or("Unranked")
+}
+
+pub fn rank_icon_url(tier: u32) -> String {
+    if tier == 0 {
+        return String::new();
+    }
+    format!("https://media.valorant-api.com/competitivetiers/03621f52-342b-cf4e-4f86-9350a49c6d04/{}/smallicon.png", tier)
+}
+
+fn cards_dir() -> PathBuf {
+    let exe = std::env::current_exe().unwrap_or_default();
+    exe.parent().unwrap_or(&PathBuf::from(".")).join("downfall_cards")
+}
+
+pub async fn render_match(update: &CompUpdate) -> Result<RgbaImage, String> {
+    let font = FontRef::try_from_slice(FONT).map_err(|e| e.to_string())?;
+
+    let (map_name, splash_url) = assets::maps().await
+        .get(&update.map_id.to_lowercase())
+        .cloned()
+        .unwrap_or(("Unknown".to_string(), String::new()));
+    let (agent_name, agent_icon_url) = assets::agents().await
+        .get(&update.agent_id)
+        .cloned()
+        .unwrap_or(("Unknown".to_string(), String::new()));
+
+    let splash = assets::fetch_image(&splash_url).await;
+    let agent_icon = assets::fetch_image(&agent_icon_url).await;
+    let rank_icon = assets::fetch_image(&rank_icon_url(update.rank_after)).await;
+
+    let mut card = RgbaImage::from_pixel(CARD_WIDTH, CARD_HEIGHT, Rgba([15, 15, 20, 255]));
+
+    if let Some(splash) = splash {
+        let cover = imageops::resize(&fit_cover(&splash, CARD_WIDTH, CARD_HEIGHT), CARD_WIDTH, CARD_HEIGHT, imageops::FilterType::Triangle);
+        imageops::overlay(&mut card, &cover, 0, 0);
+    }
+
+    for (x, _, px) in card.enumerate_pixels_mut() {
+        let shade = 0.92 - 0.45 * (x as f32 / CARD_WIDTH as f32);
+        for c in 0..3 {
+            px[c] = (px[c] as f32 * (1.0 - shade) + 15.0 * shade) as u8;
+        }
+    }
+
+    let (result, accent) = if update.rounds_won > update.rounds_lost {
+        ("VICTORY", WIN)
+    } else if update.rounds_lost > update.rounds_won {
+        ("DEFEAT", LOSS)
+    } else {
+        ("DRAW", MUTED)
+    };
+
+    for y in 0..CARD_HEIGHT {
+        for x in 0..6 {
+            card.put_pixel(x, y, accent);
+        }
+    }
+
+    draw_text(&mut card, &font, 40.0, 36, 28, accent, result);
+    draw_text(&mut card, &font, 22.0, 36, 78, WHITE, &format!("{} · {}", map_name, agent_name));
+
+    draw_text(&mut card, &font, 64.0, 36, 120, WHITE, &format!("{} – {}", update.rounds_won, update.rounds_lost));
+
+    draw_text(&mut card, &font, 14.0, 36, 222, MUTED, "K / D / A");
+    draw_text(&mut card, &font, 28.0, 36, 242, WHITE, &format!("{} / {} / {}", update.kills, update.deaths, update.assists));
+
+    if let Some(icon) = agent_icon {
+        let icon = imageops::resize(&icon, 150, 150, imageops::FilterType::Triangle);
+        imageops::overlay(&mut card, &icon, (CARD_WIDTH - 186) as i64, 28);
+    }
+
+    let rank_x = CARD_WIDTH - 260;
+    if let Some(icon) = rank_icon {
+        let icon = imageops::resize(&icon, 48, 48, imageops::FilterType::Triangle);
+        imageops::overlay(&mut card, &icon, rank_x as i64, 214);
+    }
+    draw_text(&mut card, &font, 18.0, rank_x + 60, 216, WHITE, rank_name(update.rank_after));
+
+    let rr_color = if update.rr_change > 0 { WIN } else if update.rr_change < 0 { LOSS } else { MUTED };
+    let rr_text = format!("{}{} RR", if update.rr_change > 0 { "+" } else { "" }, update.rr_change);
+    draw_text(&mut card, &font, 18.0, rank_x + 60, 242, rr_color, &rr_text);
+
+    let watermark = "Downfall";
+    let wm_width = text_width(&font, 12.0, watermark);
+    draw_text(&mut card, &font, 12.0, CARD_WIDTH - 24 - wm_width, CARD_HEIGHT - 28, MUTED, watermark);
+
+    Ok(card)
+}
+
+pub fn save(match_id: &str, card: &RgbaImage) -> Result<PathBuf, String> {
+    let dir = cards_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let path = dir.join(format!("{}.png", match_id));
+    card.save(&path).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+fn fit_cover(src: &RgbaImage, width: u32, height: u32) -> RgbaImage {
+    let (sw, sh) = src.dimensions();
+    let target_ratio = width as f32 / height as f32;
+    let (cw, ch) = if sw as f32 / sh as f32 > target_ratio {
+        ((sh as f32 * target_ratio) as u32, sh)
+    } else {
+        (sw, (sw as f32 / target_ratio) as u32)
+    };
+    imageops::crop_imm(src, (sw - cw) / 2, (sh - ch) / 2, cw, ch).to_image()
+}
+
+fn text_width(font: &FontRef, size: f32, text: &str) -> u32 {
+    let scaled = font.as_scaled(PxScale::from(size));
+    let mut width = 0.0;
+    let mut prev = None;
+    for c in text.chars() {
+        let id = scaled.glyph_id(c);
+        if let Some(p) = prev {
+            width += scaled.kern(p, id);
+        }
+        width += scaled.h_advance(id);
+        prev = Some(id);
+    }
+    width.ceil() as u32
+}
+
+fn draw_text(img: &mut RgbaImage, font: &FontRef, size: f32, x: u32, y: u32, color: Rgba<u8>, text: &str) {
+    let scaled = font.as_scaled(PxScale::from(size));
+    let mut caret = x as f32;
+    let baseline = y as f32 + scaled.ascent();
+    let mut prev = None;
+
+    for c in text.chars() {
+        let id = scaled.glyph_id(c);
+        if let Some(p) = prev {
+            caret += scaled.kern(p, id);
+        }
+        let glyph = id.with_scale_and_position(size, point(caret, baseline));
+        caret += scaled.h_advance(id);
+        prev = Some(id);
+
+        let Some(outline) = font.outline_glyph(glyph) else { continue };
+        let bounds = outline.px_bounds();
+        outline.draw(|gx, gy, coverage| {
+            let px = bounds.min.x as i32 + gx as i32;
+            let py = bounds.min.y as i32 + gy as i32;
+            if px < 0 || py < 0 || px >= img.width() as i32 || py >= img.height() as i32 {
+                return;
+            }
+            let dst = img.get_pixel_mut(px as u32, py as u32);
+            for c in 0..3 {
+                dst[c] = (dst[c] as f32 * (1.0 - coverage) + color[c] as f32 * coverage) as u8;
+            }
+        });
+    }
+}
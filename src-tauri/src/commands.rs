@@ -1,9 +1,18 @@
 use std::sync::Arc;
 use tauri::State;
-use crate::valorant::api;
+use crate::valorant::{api, form};
 use crate::valorant::connection::ValorantConnection;
-use crate::valorant::types::{ConnectionState, PlayerProfile, AgentInfo, PregameState, CurrentMatch, LiveMatch, PartyState, Friend};
+use crate::valorant::phase::{MatchPhaseTracker, PhaseSnapshot};
+use crate::valorant::score::LiveScoreTracker;
+use crate::valorant::types::{ConnectionState, PlayerProfile, AgentInfo, PregameState, CurrentMatch, LiveMatch, MatchLoadouts, PartyState, Friend, LivePresence};
 use crate::config;
+use crate::profiles;
+use crate::tray;
+use crate::automation::{self, AutomationEngine, AutomationStatus};
+use crate::card;
+use crate::summary;
+use crate::export::{self, ExportFilter, ExportFormat};
+use crate::store::{audit, dodges, encounters, history, watchlist};
 
 #[tauri::command]
 pub async fn get_connection_state(conn: State<'_, Arc<ValorantConnection>>) -> Result<ConnectionState, String> {
@@ -20,6 +29,7 @@ pub async fn get_player_profile(conn: State<'_, Arc<ValorantConnection>>) -> Res
     let account_xp = api::fetch_account_xp(&tokens, &info.puuid, &shard).await;
     let mmr = api::fetch_mmr(&tokens, &info.puuid, &shard).await;
     let comp_updates = api::fetch_comp_updates(&tokens, &info.puuid, &shard).await;
+    history::record(&info.puuid, &comp_updates).ok();
 
     Ok(PlayerProfile { info, account_xp, mmr, comp_updates })
 }
@@ -53,23 +63,94 @@ pub async fn instalock_agent(conn: State<'_, Arc<ValorantConnection>>, match_id:
     api::lock_agent(&tokens, &region, &shard, &match_id, &agent_id).await
 }
 
+#[tauri::command]
+pub async fn select_agent(conn: State<'_, Arc<ValorantConnection>>, match_id: String, agent_id: String) -> Result<(), String> {
+    let state = conn.get_state().await;
+    let region = state.region.ok_or("no region")?;
+    let shard = state.shard.ok_or("no shard")?;
+    let tokens = conn.get_tokens().await.ok_or("no auth tokens")?;
+    api::select_agent(&tokens, &region, &shard, &match_id, &agent_id).await
+}
+
 #[tauri::command]
 pub async fn dodge_match(conn: State<'_, Arc<ValorantConnection>>, match_id: String) -> Result<(), String> {
+    automation::manual_dodge(&conn, Some(&match_id)).await.map(|_| ())
+}
+
+#[tauri::command]
+pub async fn get_dodge_penalties(conn: State<'_, Arc<ValorantConnection>>) -> Result<dodges::DodgeReport, String> {
+    let state = conn.get_state().await;
+    let puuid = state.player_info.as_ref().map(|i| i.puuid.clone()).ok_or("no puuid")?;
+    let shard = state.shard.ok_or("no shard")?;
+    let restrictions = match conn.get_tokens().await {
+        Some(tokens) => api::fetch_penalties(&tokens, &shard).await,
+        None => None,
+    };
+    Ok(dodges::report(&puuid, &config::load().map_dodge, restrictions))
+}
+
+#[tauri::command]
+pub async fn get_live_match(conn: State<'_, Arc<ValorantConnection>>, app: tauri::AppHandle) -> Result<Option<LiveMatch>, String> {
     let state = conn.get_state().await;
+    let puuid = state.player_info.as_ref().map(|i| i.puuid.clone()).ok_or("no puuid")?;
     let region = state.region.ok_or("no region")?;
     let shard = state.shard.ok_or("no shard")?;
     let tokens = conn.get_tokens().await.ok_or("no auth tokens")?;
-    api::quit_pregame(&tokens, &region, &shard, &match_id).await
+    let parties = match conn.get_client_and_lock().await {
+        Some((client, lock)) => api::fetch_presence_parties(&client, &lock).await,
+        None => Default::default(),
+    };
+    let mut live = api::fetch_live_match(&tokens, &puuid, &region, &shard, &parties).await;
+    if let Some(ref mut m) = live {
+        let lobbies = encounters::record(&puuid, m);
+        encounters::annotate(&puuid, m, &lobbies);
+        watchlist::annotate(m);
+        watchlist::alert(&app, m);
+        form::prefetch(&tokens, &shard, m);
+        form::annotate(m);
+        summary::annotate(&puuid, m, &lobbies);
+    }
+    Ok(live)
+}
+
+#[tauri::command]
+pub async fn get_automation_status(engine: State<'_, Arc<AutomationEngine>>) -> Result<AutomationStatus, String> {
+    Ok(engine.status().await)
+}
+
+#[tauri::command]
+pub async fn replay_pregame(conn: State<'_, Arc<ValorantConnection>>, engine: State<'_, Arc<AutomationEngine>>, app: tauri::AppHandle, pregame: Option<PregameState>) -> Result<AutomationStatus, String> {
+    engine.replay(&app, &conn, pregame).await
+}
+
+#[tauri::command]
+pub async fn get_replay_status(engine: State<'_, Arc<AutomationEngine>>) -> Result<AutomationStatus, String> {
+    Ok(engine.replay_status().await)
+}
+
+#[tauri::command]
+pub async fn get_automation_log(limit: Option<usize>) -> Result<Vec<audit::AuditEntry>, String> {
+    Ok(audit::recent(limit.unwrap_or(200)))
+}
+
+#[tauri::command]
+pub async fn get_match_phase(tracker: State<'_, Arc<MatchPhaseTracker>>) -> Result<PhaseSnapshot, String> {
+    Ok(tracker.current().await)
 }
 
 #[tauri::command]
-pub async fn get_live_match(conn: State<'_, Arc<ValorantConnection>>) -> Result<Option<LiveMatch>, String> {
+pub async fn get_live_presences(scores: State<'_, Arc<LiveScoreTracker>>) -> Result<Vec<LivePresence>, String> {
+    Ok(scores.all().await)
+}
+
+#[tauri::command]
+pub async fn get_match_loadouts(conn: State<'_, Arc<ValorantConnection>>) -> Result<Option<MatchLoadouts>, String> {
     let state = conn.get_state().await;
     let puuid = state.player_info.as_ref().map(|i| i.puuid.clone()).ok_or("no puuid")?;
     let region = state.region.ok_or("no region")?;
     let shard = state.shard.ok_or("no shard")?;
     let tokens = conn.get_tokens().await.ok_or("no auth tokens")?;
-    Ok(api::fetch_live_match(&tokens, &puuid, &region, &shard).await)
+    Ok(api::fetch_match_loadouts(&tokens, &puuid, &region, &shard).await)
 }
 
 #[tauri::command]
@@ -212,13 +293,119 @@ pub async fn load_config() -> Result<config::AppConfig, String> {
 }
 
 #[tauri::command]
-pub async fn save_config(cfg: config::AppConfig) -> Result<(), String> {
-    config::save(&cfg)
+pub async fn save_config(app: tauri::AppHandle, cfg: config::AppConfig) -> Result<(), String> {
+    config::update(|current| {
+        let profiles = std::mem::take(&mut current.profiles);
+        let active_profile = current.active_profile.take();
+        *current = config::AppConfig { profiles, active_profile, ..cfg };
+        Ok(())
+    })?;
+    tray::sync(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn create_profile(app: tauri::AppHandle, name: String) -> Result<config::AppConfig, String> {
+    let cfg = profiles::create(&name)?;
+    config::notify(&app, &cfg);
+    Ok(cfg)
+}
+
+#[tauri::command]
+pub async fn clone_profile(app: tauri::AppHandle, id: String, name: Option<String>) -> Result<config::AppConfig, String> {
+    let cfg = profiles::duplicate(&id, name.as_deref())?;
+    config::notify(&app, &cfg);
+    Ok(cfg)
+}
+
+#[tauri::command]
+pub async fn delete_profile(app: tauri::AppHandle, id: String) -> Result<config::AppConfig, String> {
+    let cfg = profiles::delete(&id)?;
+    config::notify(&app, &cfg);
+    Ok(cfg)
+}
+
+#[tauri::command]
+pub async fn set_profile_activation(app: tauri::AppHandle, id: String, activation: config::ProfileActivation) -> Result<config::AppConfig, String> {
+    let cfg = profiles::set_activation(&id, activation)?;
+    config::notify(&app, &cfg);
+    Ok(cfg)
+}
+
+#[tauri::command]
+pub async fn activate_profile(app: tauri::AppHandle, id: String) -> Result<config::AppConfig, String> {
+    profiles::activate(&app, &id)
 }
 
 #[tauri::command]
 pub async fn get_current_match(conn: State<'_, Arc<ValorantConnection>>) -> Result<Option<CurrentMatch>, String> {
     let (client, lock) = conn.get_client_and_lock().await
         .ok_or_else(|| "not connected".to_string())?;
-    api::fetch_current_match(&client, &lock).await.map_err(|e| e.to_string())
+    let state = conn.get_state().await;
+    let region = state.region.ok_or("no region")?;
+    let shard = state.shard.ok_or("no shard")?;
+    let tokens = conn.get_tokens().await.ok_or("no auth tokens")?;
+    api::fetch_current_match(&client, &lock, &tokens, &region, &shard).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn render_match_card(conn: State<'_, Arc<ValorantConnection>>, app: tauri::AppHandle, match_id: String, copy: bool) -> Result<String, String> {
+    let state = conn.get_state().await;
+    let info = state.player_info.ok_or("not connected")?;
+    let shard = state.shard.ok_or("no shard")?;
+    let tokens = conn.get_tokens().await.ok_or("no auth tokens")?;
+
+    let update = api::fetch_comp_updates(&tokens, &info.puuid, &shard).await
+        .into_iter()
+        .find(|u| u.match_id == match_id)
+        .ok_or("match not found")?;
+
+    let image = card::render_match(&update).await?;
+    let path = card::save(&match_id, &image)?;
+
+    if copy {
+        use tauri_plugin_clipboard_manager::ClipboardExt;
+        let (w, h) = image.dimensions();
+        app.clipboard()
+            .write_image(&tauri::image::Image::new_owned(image.into_raw(), w, h))
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub async fn export_matches(conn: State<'_, Arc<ValorantConnection>>, format: ExportFormat, filter: ExportFilter) -> Result<String, String> {
+    let state = conn.get_state().await;
+    let info = state.player_info.ok_or("not connected")?;
+    let matches = export::apply_filter(history::matches(&info.puuid), &filter);
+    let agents = api::fetch_agent_map().await;
+    let rows = export::match_rows(&matches, &agents);
+    let path = export::write("matches", format, &rows)?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub async fn export_sessions(conn: State<'_, Arc<ValorantConnection>>, format: ExportFormat, filter: ExportFilter) -> Result<String, String> {
+    let state = conn.get_state().await;
+    let info = state.player_info.ok_or("not connected")?;
+    let matches = export::apply_filter(history::matches(&info.puuid), &filter);
+    let rows = export::session_rows(&matches);
+    let path = export::write("sessions", format, &rows)?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+pub async fn get_watchlist() -> Result<Vec<watchlist::WatchEntry>, String> {
+    Ok(watchlist::all())
+}
+
+#[tauri::command]
+pub async fn set_watchlist_entry(entry: watchlist::WatchEntry) -> Result<watchlist::WatchEntry, String> {
+    watchlist::upsert(entry)
+}
+
+#[tauri::command]
+pub async fn remove_watchlist_entry(puuid: String) -> Result<(), String> {
+    watchlist::remove(&puuid)
 }
@@ -1,8 +1,11 @@
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::RwLock;
+use tauri::{AppHandle, Emitter};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AppConfig {
     #[serde(default)]
@@ -15,80 +18,336 @@ pub struct AppConfig {
     pub app: AppBehaviorConfig,
     #[serde(default)]
     pub discord: DiscordRpcConfig,
+    #[serde(default)]
+    pub queues: HashMap<String, QueueProfile>,
+    #[serde(default)]
+    pub audit: AuditLogConfig,
+    #[serde(default)]
+    pub automation: AutomationConfig,
+    #[serde(default)]
+    pub profiles: Vec<Profile>,
+    #[serde(default)]
+    pub active_profile: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct InstalockConfig {
+pub struct Profile {
+    pub id: String,
+    pub name: String,
     #[serde(default)]
-    pub active: bool,
+    pub instalock: InstalockConfig,
     #[serde(default)]
-    pub default_agent: Option<String>,
+    pub map_dodge: MapDodgeConfig,
+    #[serde(default)]
+    pub timing: TimingConfig,
+    #[serde(default)]
+    pub discord: DiscordRpcConfig,
     #[serde(default)]
-    pub map_overrides: HashMap<String, String>,
+    pub activation: ProfileActivation,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct MapDodgeConfig {
+pub struct ProfileActivation {
     #[serde(default)]
-    pub active: bool,
+    pub enabled: bool,
     #[serde(default)]
-    pub blacklisted_maps: Vec<String>,
+    pub timezone: String,
+    #[serde(default)]
+    pub windows: Vec<ScheduleWindow>,
+    #[serde(default)]
+    pub party_with: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct TimingConfig {
-    #[serde(default = "default_preset")]
-    pub preset: String,
+pub struct ScheduleWindow {
     #[serde(default)]
-    pub select_delay: u32,
+    pub days: Vec<String>,
+    pub start: String,
+    pub end: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationConfig {
     #[serde(default)]
-    pub lock_delay: u32,
+    pub dry_run: bool,
 }
 
-fn default_preset() -> String {
-    "instant".to_string()
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueProfile {
+    #[serde(default = "default_true")]
+    pub instalock: bool,
+    #[serde(default = "default_true")]
+    pub dodge: bool,
+    #[serde(default)]
+    pub agent_priority: Vec<String>,
+    #[serde(default)]
+    pub map_priority: HashMap<String, Vec<String>>,
 }
 
-impl Default for AppConfig {
+impl Default for QueueProfile {
     fn default() -> Self {
         Self {
-            instalock: InstalockConfig::default(),
-            map_dodge: MapDodgeConfig::default(),
-            timing: TimingConfig::default(),
-            app: AppBehaviorConfig::default(),
-            discord: DiscordRpcConfig::default(),
+            instalock: true,
+            dodge: true,
+            agent_priority: Vec::new(),
+            map_priority: HashMap::new(),
+        }
+    }
+}
+
+pub fn queue_key(queue_id: &str) -> &str {
+    if queue_id.is_empty() { "custom" } else { queue_id }
+}
+
+impl AppConfig {
+    pub fn snapshot(&self, id: String, name: String) -> Profile {
+        Profile {
+            id,
+            name,
+            instalock: self.instalock.clone(),
+            map_dodge: self.map_dodge.clone(),
+            timing: self.timing.clone(),
+            discord: self.discord.clone(),
+            activation: ProfileActivation::default(),
+        }
+    }
+
+    pub fn apply_profile(&mut self, profile: &Profile) {
+        self.instalock = profile.instalock.clone();
+        self.map_dodge = profile.map_dodge.clone();
+        self.timing = profile.timing.clone();
+        self.discord = profile.discord.clone();
+        self.active_profile = Some(profile.id.clone());
+    }
+
+    fn sync_active_profile(&mut self) {
+        let Some(id) = self.active_profile.clone() else {
+            return;
+        };
+        let Some(profile) = self.profiles.iter_mut().find(|p| p.id == id) else {
+            self.active_profile = None;
+            return;
+        };
+        profile.instalock = self.instalock.clone();
+        profile.map_dodge = self.map_dodge.clone();
+        profile.timing = self.timing.clone();
+        profile.discord = self.discord.clone();
+    }
+
+    pub fn queue_profile(&self, queue_id: &str) -> Option<&QueueProfile> {
+        self.queues.get(queue_key(queue_id))
+    }
+
+    pub fn instalock_enabled_for(&self, queue_id: &str) -> bool {
+        self.instalock.active && self.queue_profile(queue_id).is_none_or(|q| q.instalock)
+    }
+
+    pub fn dodge_enabled_for(&self, queue_id: &str) -> bool {
+        self.map_dodge.active && self.queue_profile(queue_id).is_none_or(|q| q.dodge)
+    }
+
+    pub fn priority_for(&self, queue_id: &str, map_name: &str) -> Vec<String> {
+        match self.queue_profile(queue_id) {
+            Some(q) if !q.agent_priority.is_empty() || !q.map_priority.is_empty() => {
+                merge_priority(q.map_priority.get(map_name), &q.agent_priority)
+            }
+            _ => self.instalock.priority_for(map_name),
         }
     }
 }
 
+fn merge_priority(per_map: Option<&Vec<String>>, global: &[String]) -> Vec<String> {
+    let mut list: Vec<String> = per_map.cloned().unwrap_or_default();
+    for agent in global {
+        if !list.contains(agent) {
+            list.push(agent.clone());
+        }
+    }
+    list
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalockConfig {
+    #[serde(default)]
+    pub active: bool,
+    #[serde(default)]
+    pub agent_priority: Vec<String>,
+    #[serde(default)]
+    pub map_priority: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub hover_only: bool,
+    #[serde(default)]
+    pub auto_lock: bool,
+    #[serde(default = "default_auto_lock_secs")]
+    pub auto_lock_secs: u32,
+    #[serde(default, skip_serializing)]
+    default_agent: Option<String>,
+    #[serde(default, skip_serializing)]
+    map_overrides: HashMap<String, String>,
+}
+
+fn default_auto_lock_secs() -> u32 { 5 }
+
 impl Default for InstalockConfig {
     fn default() -> Self {
         Self {
             active: false,
+            agent_priority: Vec::new(),
+            map_priority: HashMap::new(),
+            hover_only: false,
+            auto_lock: false,
+            auto_lock_secs: 5,
             default_agent: None,
             map_overrides: HashMap::new(),
         }
     }
 }
 
+impl InstalockConfig {
+    fn migrate(&mut self) {
+        if let Some(agent) = self.default_agent.take() {
+            if self.agent_priority.is_empty() {
+                self.agent_priority.push(agent);
+            }
+        }
+        for (map, agent) in self.map_overrides.drain() {
+            self.map_priority.entry(map).or_insert_with(|| vec![agent]);
+        }
+    }
+
+    pub fn priority_for(&self, map_name: &str) -> Vec<String> {
+        merge_priority(self.map_priority.get(map_name), &self.agent_priority)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MapDodgeConfig {
+    #[serde(default)]
+    pub active: bool,
+    #[serde(default)]
+    pub blacklisted_maps: Vec<String>,
+    #[serde(default)]
+    pub rules: Vec<DodgeRule>,
+    #[serde(default)]
+    pub test_mode: bool,
+    #[serde(default)]
+    pub max_auto_dodges: u32,
+    #[serde(default = "default_dodge_window_hours")]
+    pub dodge_window_hours: u32,
+}
+
+fn default_dodge_window_hours() -> u32 { 24 }
+
 impl Default for MapDodgeConfig {
     fn default() -> Self {
         Self {
             active: false,
             blacklisted_maps: Vec::new(),
+            rules: Vec::new(),
+            test_mode: false,
+            max_auto_dodges: 0,
+            dodge_window_hours: 24,
         }
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DodgeRule {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub conditions: Vec<DodgeCondition>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum DodgeCondition {
+    Map { maps: Vec<String> },
+    Queue { queues: Vec<String> },
+    Watchlisted {
+        #[serde(default)]
+        tags: Vec<String>,
+    },
+    DuplicateRole {
+        #[serde(default)]
+        roles: Vec<String>,
+    },
+    AllyRankBelow { tier: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum DelayDistribution {
+    Fixed { ms: u32 },
+    Uniform { min: u32, max: u32 },
+    #[serde(rename_all = "camelCase")]
+    Normal { mean: u32, std_dev: u32, min: u32, max: u32 },
+}
+
+impl Default for DelayDistribution {
+    fn default() -> Self {
+        DelayDistribution::Fixed { ms: 0 }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimingConfig {
+    #[serde(default = "default_preset")]
+    pub preset: String,
+    #[serde(default)]
+    pub select: DelayDistribution,
+    #[serde(default)]
+    pub lock: DelayDistribution,
+    #[serde(default)]
+    pub min_gap: u32,
+    #[serde(default = "default_deadline_secs")]
+    pub deadline_secs: u32,
+    #[serde(default, skip_serializing)]
+    select_delay: Option<u32>,
+    #[serde(default, skip_serializing)]
+    lock_delay: Option<u32>,
+}
+
+fn default_preset() -> String {
+    "instant".to_string()
+}
+
+fn default_deadline_secs() -> u32 { 5 }
+
 impl Default for TimingConfig {
     fn default() -> Self {
         Self {
             preset: "instant".to_string(),
-            select_delay: 0,
-            lock_delay: 0,
+            select: DelayDistribution::default(),
+            lock: DelayDistribution::default(),
+            min_gap: 0,
+            deadline_secs: 5,
+            select_delay: None,
+            lock_delay: None,
+        }
+    }
+}
+
+impl TimingConfig {
+    fn migrate(&mut self) {
+        if let Some(ms) = self.select_delay.take() {
+            self.select = DelayDistribution::Fixed { ms };
+        }
+        if let Some(ms) = self.lock_delay.take() {
+            self.lock = DelayDistribution::Fixed { ms };
         }
     }
 }
@@ -100,6 +359,8 @@ pub struct AppBehaviorConfig {
     pub minimize_on_close: bool,
     #[serde(default)]
     pub start_minimized: bool,
+    #[serde(default = "default_true")]
+    pub post_match_notifications: bool,
 }
 
 impl Default for AppBehaviorConfig {
@@ -107,6 +368,7 @@ impl Default for AppBehaviorConfig {
         Self {
             minimize_on_close: false,
             start_minimized: false,
+            post_match_notifications: true,
         }
     }
 }
@@ -120,6 +382,8 @@ pub struct DiscordRpcConfig {
     pub details: String,
     #[serde(default)]
     pub state: String,
+    #[serde(default = "default_true")]
+    pub show_match_score: bool,
 }
 
 fn default_true() -> bool { true }
@@ -131,6 +395,31 @@ impl Default for DiscordRpcConfig {
             enabled: true,
             details: "Playing Valorant with Downfall".to_string(),
             state: String::new(),
+            show_match_score: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_audit_entries")]
+    pub max_entries: u32,
+    #[serde(default = "default_audit_days")]
+    pub retention_days: u32,
+}
+
+fn default_audit_entries() -> u32 { 2000 }
+fn default_audit_days() -> u32 { 30 }
+
+impl Default for AuditLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_entries: default_audit_entries(),
+            retention_days: default_audit_days(),
         }
     }
 }
@@ -140,16 +429,58 @@ fn config_path() -> PathBuf {
     exe.parent().unwrap_or(&PathBuf::from(".")).join("downfall_config.json")
 }
 
-pub fn load() -> AppConfig {
+static CONFIG: Lazy<RwLock<Option<AppConfig>>> = Lazy::new(|| RwLock::new(None));
+
+fn read_from_disk() -> AppConfig {
     let path = config_path();
-    match std::fs::read_to_string(&path) {
-        Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
+    let mut config: AppConfig = match std::fs::read_to_string(&path) {
+        Ok(data) => match serde_json::from_str(&data) {
+            Ok(config) => config,
+            Err(e) => {
+                let backup = path.with_extension("json.bak");
+                println!("[config] failed to parse {}: {}, moving it to {}", path.display(), e, backup.display());
+                if let Err(e) = std::fs::rename(&path, &backup) {
+                    println!("[config] failed to back up {}: {}", path.display(), e);
+                }
+                AppConfig::default()
+            }
+        },
         Err(_) => AppConfig::default(),
+    };
+    config.instalock.migrate();
+    config.timing.migrate();
+    config.sync_active_profile();
+    config
+}
+
+pub fn load() -> AppConfig {
+    if let Some(config) = CONFIG.read().unwrap().as_ref() {
+        return config.clone();
     }
+    let mut cached = CONFIG.write().unwrap();
+    cached.get_or_insert_with(read_from_disk).clone()
 }
 
-pub fn save(config: &AppConfig) -> Result<(), String> {
+fn write(cached: &mut Option<AppConfig>, mut config: AppConfig) -> Result<AppConfig, String> {
+    config.instalock.migrate();
+    config.timing.migrate();
+    config.sync_active_profile();
+
     let path = config_path();
-    let data = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
-    std::fs::write(&path, data).map_err(|e| e.to_string())
+    let data = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    std::fs::write(&path, data).map_err(|e| e.to_string())?;
+    *cached = Some(config.clone());
+    Ok(config)
+}
+
+pub fn notify(app: &AppHandle, config: &AppConfig) {
+    let _ = app.emit("config-changed", config);
+    crate::tray::sync(app);
+}
+
+pub fn update(apply: impl FnOnce(&mut AppConfig) -> Result<(), String>) -> Result<AppConfig, String> {
+    let mut cached = CONFIG.write().unwrap();
+    let mut config = cached.get_or_insert_with(read_from_disk).clone();
+    apply(&mut config)?;
+    write(&mut cached, config)
 }
@@ -1,5 +1,6 @@
 use std::sync::Mutex;
 use discord_rich_presence::{activity, DiscordIpc, DiscordIpcClient};
+use crate::config::DiscordRpcConfig;
 
 const CLIENT_ID: &str = "1469360807132528660";
 
@@ -71,3 +72,17 @@ pub fn update_presence(details: &str, state: &str) {
 pub fn is_connected() -> bool {
     *RPC_CONNECTED.lock().unwrap()
 }
+
+pub fn sync(cfg: &DiscordRpcConfig, live_state: Option<&str>) {
+    if !cfg.enabled {
+        if is_connected() {
+            disconnect();
+        }
+        return;
+    }
+    if !is_connected() {
+        connect();
+    }
+    let state = live_state.filter(|_| cfg.show_match_score).unwrap_or(&cfg.state);
+    update_presence(&cfg.details, state);
+}
@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::card::rank_name;
+use crate::valorant::api::resolve_map_name;
+use crate::valorant::types::CompUpdate;
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+const SESSION_GAP_MS: u64 = 3 * 60 * 60 * 1000;
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportFilter {
+    #[serde(default)]
+    pub from: Option<u64>,
+    #[serde(default)]
+    pub to: Option<u64>,
+    #[serde(default)]
+    pub queue: Option<String>,
+    #[serde(default)]
+    pub map: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchRow {
+    pub date: String,
+    pub match_id: String,
+    pub queue: String,
+    pub map: String,
+    pub agent: String,
+    pub result: String,
+    pub rounds_won: u32,
+    pub rounds_lost: u32,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+    pub kd_ratio: f64,
+    pub score: u32,
+    pub score_per_round: f64,
+    pub rank_before: String,
+    pub rank_after: String,
+    pub rr_before: u32,
+    pub rr_after: u32,
+    pub rr_change: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionRow {
+    pub session_start: String,
+    pub session_end: String,
+    pub matches: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub win_rate: f64,
+    pub rr_change: i32,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+    pub kd_ratio: f64,
+    pub rank_start: String,
+    pub rank_end: String,
+}
+
+pub trait CsvRow: Serialize {
+    const HEADERS: &'static [&'static str];
+}
+
+impl CsvRow for MatchRow {
+    const HEADERS: &'static [&'static str] = &[
+        "date", "match_id", "queue", "map", "agent", "result", "rounds_won", "rounds_lost",
+        "kills", "deaths", "assists", "kd_ratio", "score", "score_per_round",
+        "rank_before", "rank_after", "rr_before", "rr_after", "rr_change",
+    ];
+}
+
+impl CsvRow for SessionRow {
+    const HEADERS: &'static [&'static str] = &[
+        "session_start", "session_end", "matches", "wins", "losses", "draws", "win_rate",
+        "rr_change", "kills", "deaths", "assists", "kd_ratio", "rank_start", "rank_end",
+    ];
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonExport<'a, T: Serialize> {
+    schema_version: u32,
+    kind: &'a str,
+    rows: &'a [T],
+}
+
+fn exports_dir() -> PathBuf {
+    let exe = std::env::current_exe().unwrap_or_default();
+    exe.parent().unwrap_or(&PathBuf::from(".")).join("downfall_exports")
+}
+
+fn format_date(timestamp_ms: u64) -> String {
+    DateTime::<Utc>::from_timestamp_millis(timestamp_ms as i64)
+        .map(|d| d.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+        .unwrap_or_default()
+}
+
+fn ratio(a: u32, b: u32) -> f64 {
+    let value = if b == 0 { a as f64 } else { a as f64 / b as f64 };
+    (value * 100.0).round() / 100.0
+}
+
+pub fn match_result(update: &CompUpdate) -> &'static str {
+    if update.rounds_won > update.rounds_lost {
+        "win"
+    } else if update.rounds_lost > update.rounds_won {
+        "loss"
+    } else {
+        "draw"
+    }
+}
+
+pub fn apply_filter(matches: Vec<CompUpdate>, filter: &ExportFilter) -> Vec<CompUpdate> {
+    matches.into_iter()
+        .filter(|m| filter.from.is_none_or(|from| m.timestamp >= from))
+        .filter(|m| filter.to.is_none_or(|to| m.timestamp <= to))
+        .filter(|m| filter.queue.as_deref().is_none_or(|q| q.is_empty() || m.queue_id.eq_ignore_ascii_case(q)))
+        .filter(|m| filter.map.as_deref().is_none_or(|map| map.is_empty() || resolve_map_name(&m.map_id).eq_ignore_ascii_case(map)))
+        .collect()
+}
+
+pub fn match_rows(matches: &[CompUpdate], agents: &HashMap<String, (String, String)>) -> Vec<MatchRow> {
+    matches.iter().map(|m| {
+        let rounds = m.rounds_won + m.rounds_lost;
+        MatchRow {
+            date: format_date(m.timestamp),
+            match_id: m.match_id.clone(),
+            queue: m.queue_id.clone(),
+            map: resolve_map_name(&m.map_id),
+            agent: agents.get(&m.agent_id).map(|(name, _)| name.clone()).unwrap_or_else(|| m.agent_id.clone()),
+            result: match_result(m).to_string(),
+            rounds_won: m.rounds_won,
+            rounds_lost: m.rounds_lost,
+            kills: m.kills,
+            deaths: m.deaths,
+            assists: m.assists,
+            kd_ratio: ratio(m.kills, m.deaths),
+            score: m.score,
+            score_per_round: if rounds == 0 { 0.0 } else { ratio(m.score, rounds) },
+            rank_before: rank_name(m.rank_before).to_string(),
+            rank_after: rank_name(m.rank_after).to_string(),
+            rr_before: m.rr_before,
+            rr_after: m.rr_after,
+            rr_change: m.rr_change,
+        }
+    }).collect()
+}
+
+pub fn session_rows(matches: &[CompUpdate]) -> Vec<SessionRow> {
+    let mut sorted: Vec<&CompUpdate> = matches.iter().collect();
+    sorted.sort_by_key(|m| m.timestamp);
+
+    let mut sessions: Vec<Vec<&CompUpdate>> = Vec::new();
+    for m in sorted {
+        match sessions.last_mut() {
+            Some(current) if m.timestamp.saturating_sub(current.last().unwrap().timestamp) <= SESSION_GAP_MS => current.push(m),
+            _ => sessions.push(vec![m]),
+        }
+    }
+
+    sessions.iter().map(|games| {
+        let first = games.first().unwrap();
+        let last = games.last().unwrap();
+        let count = |result: &str| games.iter().filter(|m| match_result(m) == result).count() as u32;
+        let wins = count("win");
+        let kills = games.iter().map(|m| m.kills).sum();
+        let deaths = games.iter().map(|m| m.deaths).sum();
+
+        SessionRow {
+            session_start: format_date(first.timestamp),
+            session_end: format_date(last.timestamp),
+            matches: games.len() as u32,
+            wins,
+            losses: count("loss"),
+            draws: count("draw"),
+            win_rate: ratio(wins * 100, games.len() as u32),
+            rr_change: games.iter().map(|m| m.rr_change).sum(),
+            kills,
+            deaths,
+            assists: games.iter().map(|m| m.assists).sum(),
+            kd_ratio: ratio(kills, deaths),
+            rank_start: rank_name(first.rank_before).to_string(),
+            rank_end: rank_name(last.rank_after).to_string(),
+        }
+    }).collect()
+}
+
+pub fn write<T: CsvRow>(kind: &str, format: ExportFormat, rows: &[T]) -> Result<PathBuf, String> {
+    let dir = exports_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let stamp = Utc::now().format("%Y%m%d-%H%M%S");
+    let ext = match format { ExportFormat::Csv => "csv", ExportFormat::Json => "json" };
+    let path = dir.join(format!("{}-{}.{}", kind, stamp, ext));
+
+    match format {
+        ExportFormat::Csv => {
+            let mut writer = csv::WriterBuilder::new()
+                .has_headers(false)
+                .from_path(&path)
+                .map_err(|e| e.to_string())?;
+            writer.write_record(T::HEADERS).map_err(|e| e.to_string())?;
+            for row in rows {
+                writer.serialize(row).map_err(|e| e.to_string())?;
+            }
+            writer.flush().map_err(|e| e.to_string())?;
+        }
+        ExportFormat::Json => {
+            let payload = JsonExport { schema_version: SCHEMA_VERSION, kind, rows };
+            let data = serde_json::to_string_pretty(&payload).map_err(|e| e.to_string())?;
+            std::fs::write(&path, data).map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR_MS: u64 = 60 * 60 * 1000;
+
+    fn game(match_id: &str, timestamp: u64, rounds_won: u32, rounds_lost: u32) -> CompUpdate {
+        CompUpdate {
+            match_id: match_id.to_string(),
+            queue_id: "competitive".to_string(),
+            timestamp,
+            rounds_won,
+            rounds_lost,
+            ..Default::default()
+        }
+    }
+
+    fn header<T: CsvRow>(row: &T) -> String {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.serialize(row).unwrap();
+        let data = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        data.lines().next().unwrap().to_string()
+    }
+
+    #[test]
+    fn sessions_split_after_three_hour_gap() {
+        let games = [
+            game("c", 2 * SESSION_GAP_MS + 1, 13, 5),
+            game("a", 0, 13, 5),
+            game("b", SESSION_GAP_MS, 5, 13),
+        ];
+        let rows = session_rows(&games);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].matches, 2);
+        assert_eq!(rows[1].matches, 1);
+        assert_eq!(rows[1].session_start, format_date(2 * SESSION_GAP_MS + 1));
+    }
+
+    #[test]
+    fn gap_is_measured_from_the_previous_match() {
+        let games: Vec<CompUpdate> = (0..4).map(|i| game(&i.to_string(), i * 2 * HOUR_MS, 13, 5)).collect();
+        let rows = session_rows(&games);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].matches, 4);
+    }
+
+    #[test]
+    fn session_totals_results_and_ranks() {
+        let mut first = game("a", 0, 13, 5);
+        first.rank_before = 12;
+        first.rr_change = 20;
+        first.kills = 20;
+        first.deaths = 10;
+        let mut second = game("b", HOUR_MS, 5, 13);
+        second.rr_change = -15;
+        second.deaths = 10;
+        let mut third = game("c", 2 * HOUR_MS, 12, 12);
+        third.rank_after = 13;
+        third.kills = 10;
+
+        let rows = session_rows(&[first, second, third]);
+        let row = &rows[0];
+        assert_eq!((row.wins, row.losses, row.draws), (1, 1, 1));
+        assert_eq!(row.win_rate, 33.33);
+        assert_eq!(row.rr_change, 5);
+        assert_eq!(row.kd_ratio, 1.5);
+        assert_eq!(row.rank_start, "Gold 1");
+        assert_eq!(row.rank_end, "Gold 2");
+    }
+
+    #[test]
+    fn no_matches_means_no_sessions() {
+        assert!(session_rows(&[]).is_empty());
+    }
+
+    #[test]
+    fn filter_bounds_are_inclusive() {
+        let games = vec![game("a", 100, 13, 5), game("b", 200, 13, 5), game("c", 300, 13, 5)];
+        let filter = ExportFilter { from: Some(200), to: Some(300), queue: Some("Competitive".to_string()), map: None };
+        let ids: Vec<String> = apply_filter(games, &filter).into_iter().map(|m| m.match_id).collect();
+        assert_eq!(ids, vec!["b".to_string(), "c".to_string()]);
+    }
+
+    #[test]
+    fn csv_headers_match_row_fields() {
+        let matches = match_rows(&[game("a", 0, 13, 5)], &HashMap::new());
+        assert_eq!(header(&matches[0]), MatchRow::HEADERS.join(","));
+        let sessions = session_rows(&[game("a", 0, 13, 5)]);
+        assert_eq!(header(&sessions[0]), SessionRow::HEADERS.join(","));
+    }
+}
@@ -2,31 +2,52 @@ mod valorant;
 mod commands;
 mod config;
 mod discord;
+mod assets;
+mod automation;
+mod card;
+mod export;
+mod postmatch;
+mod profiles;
+mod store;
+mod summary;
+mod tray;
 
 use std::sync::Arc;
-use commands::{get_connection_state, get_player_profile, get_agents, get_pregame_state, instalock_agent, dodge_match, get_live_match, get_party, party_invite, party_kick, party_accept_invite, party_decline_invite, party_promote, party_set_accessibility, party_set_ready, party_queue, party_set_queue, party_generate_code, party_disable_code, get_friends, minimize_to_tray, load_config, save_config, get_current_match};
+use commands::{get_connection_state, get_player_profile, get_agents, get_pregame_state, instalock_agent, select_agent, dodge_match, get_live_match, get_match_loadouts, get_party, party_invite, party_kick, party_accept_invite, party_decline_invite, party_promote, party_set_accessibility, party_set_ready, party_queue, party_set_queue, party_generate_code, party_disable_code, get_friends, minimize_to_tray, load_config, save_config, create_profile, clone_profile, delete_profile, activate_profile, set_profile_activation, get_current_match, render_match_card, export_matches, export_sessions, get_watchlist, set_watchlist_entry, remove_watchlist_entry, get_match_phase, get_live_presences, get_automation_status, replay_pregame, get_replay_status, get_automation_log, get_dodge_penalties};
+use automation::AutomationEngine;
+use profiles::ProfileScheduler;
 use valorant::connection::ValorantConnection;
+use valorant::phase::{MatchPhase, MatchPhaseTracker};
+use valorant::score::{self, LiveScoreTracker};
 use valorant::types::ConnectionStatus;
-use tauri::{image::Image, Manager};
-use tauri::tray::TrayIconBuilder;
+use tauri::{image::Image, Emitter, Manager};
 use tauri_plugin_notification::NotificationExt;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     let connection = Arc::new(ValorantConnection::new());
+    let phase_tracker = Arc::new(MatchPhaseTracker::new());
+    let score_tracker = Arc::new(LiveScoreTracker::new());
+    let automation = Arc::new(AutomationEngine::new());
 
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
         .manage(connection.clone())
+        .manage(phase_tracker.clone())
+        .manage(score_tracker.clone())
+        .manage(automation.clone())
         .invoke_handler(tauri::generate_handler![
             get_connection_state,
             get_player_profile,
             get_agents,
             get_pregame_state,
             instalock_agent,
+            select_agent,
             dodge_match,
             get_live_match,
+            get_match_loadouts,
             get_party,
             party_invite,
             party_kick,
@@ -43,31 +64,36 @@ pub fn run() {
             minimize_to_tray,
             load_config,
             save_config,
+            create_profile,
+            clone_profile,
+            delete_profile,
+            activate_profile,
+            set_profile_activation,
             get_current_match,
+            render_match_card,
+            export_matches,
+            export_sessions,
+            get_watchlist,
+            set_watchlist_entry,
+            remove_watchlist_entry,
+            get_match_phase,
+            get_live_presences,
+            get_automation_status,
+            replay_pregame,
+            get_replay_status,
+            get_automation_log,
+            get_dodge_penalties,
         ])
         .setup(move |app| {
             let png = image::load_from_memory(include_bytes!("../icons/downfall.png"))
                 .expect("failed to load icon")
                 .to_rgba8();
             let (w, h) = png.dimensions();
-            let icon = Image::new_owned(png.into_raw(), w, h);
+            let icon = Image::new_owned(png.clone().into_raw(), w, h);
             let window = app.get_webview_window("main").unwrap();
-            window.set_icon(icon.clone())?;
+            window.set_icon(icon)?;
 
-            let app_handle = app.handle().clone();
-            TrayIconBuilder::new()
-                .icon(icon)
-                .tooltip("Downfall")
-                .on_tray_icon_event(move |_tray, event| {
-                    if let tauri::tray::TrayIconEvent::Click { button: tauri::tray::MouseButton::Left, .. } = event {
-                        if let Some(w) = app_handle.get_webview_window("main") {
-                            let _ = w.show();
-                            let _ = w.unminimize();
-                            let _ = w.set_focus();
-                        }
-                    }
-                })
-                .build(app)?;
+            tray::create(app.handle(), png)?;
 
             let cfg = config::load();
             if cfg.app.start_minimized {
@@ -89,6 +115,7 @@ pub fn run() {
             }
 
             let conn = connection.clone();
+            let scores = score_tracker.clone();
             tauri::async_runtime::spawn(async move {
                 let mut rpc_tick: u32 = 0;
                 loop {
@@ -105,17 +132,12 @@ pub fn run() {
                     }
 
                     rpc_tick += 1;
-                    if rpc_tick % 10 == 0 {
+                    if rpc_tick == 10 {
+                        rpc_tick = 0;
                         let rpc_cfg = config::load().discord;
+                        let live_state = scores.own().await.as_ref().and_then(score::describe);
                         tokio::task::spawn_blocking(move || {
-                            if rpc_cfg.enabled {
-                                if !discord::is_connected() {
-                                    discord::connect();
-                                }
-                                discord::update_presence(&rpc_cfg.details, &rpc_cfg.state);
-                            } else if discord::is_connected() {
-                                discord::disconnect();
-                            }
+                            discord::sync(&rpc_cfg, live_state.as_deref());
                         });
                     }
 
@@ -123,6 +145,64 @@ pub fn run() {
                 }
             });
 
+            let conn = connection.clone();
+            let tracker = phase_tracker.clone();
+            let scores = score_tracker.clone();
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                loop {
+                    for change in scores.poll(&conn).await {
+                        let _ = app_handle.emit("presence-update", &change);
+                        if !change.presence.is_self {
+                            continue;
+                        }
+                        if change.score_changed {
+                            let _ = app_handle.emit("live-score", &change.presence);
+                        }
+                        let rpc_cfg = config::load().discord;
+                        let live_state = score::describe(&change.presence);
+                        tokio::task::spawn_blocking(move || {
+                            discord::sync(&rpc_cfg, live_state.as_deref());
+                        });
+                    }
+
+                    if let Some(transition) = tracker.poll(&conn).await {
+                        let _ = app_handle.emit("match-phase", &transition);
+
+                        let left_game = transition.from == MatchPhase::InGame
+                            && !matches!(transition.to, MatchPhase::InGame | MatchPhase::Pregame);
+                        if left_game && !transition.finished_match_id.is_empty() && config::load().app.post_match_notifications {
+                            postmatch::spawn(app_handle.clone(), conn.clone(), transition.finished_match_id.clone());
+                        }
+                    }
+
+                    let state = conn.get_state().await;
+                    tray::set_status(&app_handle, &state);
+                    tray::update(&app_handle, &state, &tracker.current().await, scores.own().await.as_ref());
+                    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+                }
+            });
+
+            let conn = connection.clone();
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let mut scheduler = ProfileScheduler::default();
+                loop {
+                    scheduler.tick(&app_handle, &conn).await;
+                    tokio::time::sleep(std::time::Duration::from_secs(15)).await;
+                }
+            });
+
+            let conn = connection.clone();
+            let engine = automation.clone();
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                loop {
+                    engine.tick(&app_handle, &conn).await;
+                    tokio::time::sleep(automation::TICK).await;
+                }
+            });
+
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+use crate::export::match_result;
+use crate::store::history;
+use crate::valorant::api;
+use crate::valorant::connection::ValorantConnection;
+use crate::valorant::types::CompUpdate;
+
+const RETRY_INTERVAL: Duration = Duration::from_secs(10);
+const MAX_ATTEMPTS: u32 = 12;
+
+fn summary(update: &CompUpdate) -> (String, String) {
+    let title = match match_result(update) {
+        _ if update.rounds_won + update.rounds_lost == 0 => "Match complete",
+        "win" => "Victory",
+        "loss" => "Defeat",
+        _ => "Draw",
+    };
+
+    let mut parts = Vec::new();
+    if update.rounds_won + update.rounds_lost > 0 {
+        parts.push(format!("{}–{}", update.rounds_won, update.rounds_lost));
+    }
+    parts.push(format!("{}/{}/{}", update.kills, update.deaths, update.assists));
+    if update.queue_id == "competitive" {
+        parts.push(format!("{}{} RR", if update.rr_change > 0 { "+" } else { "" }, update.rr_change));
+    }
+
+    (format!("{} on {}", title, api::resolve_map_name(&update.map_id)), parts.join(" · "))
+}
+
+pub fn spawn(app: AppHandle, conn: Arc<ValorantConnection>, match_id: String) {
+    tauri::async_runtime::spawn(async move {
+        for _ in 0..MAX_ATTEMPTS {
+            tokio::time::sleep(RETRY_INTERVAL).await;
+
+            let state = conn.get_state().await;
+            let (Some(puuid), Some(shard), Some(tokens)) = (
+                state.player_info.as_ref().map(|i| i.puuid.clone()),
+                state.shard,
+                conn.get_tokens().await,
+            ) else {
+                continue;
+            };
+
+            let updates = api::fetch_comp_updates(&tokens, &puuid, &shard).await;
+            let Some(update) = updates.iter().find(|u| u.match_id == match_id) else { continue };
+
+            history::record(&puuid, &updates).ok();
+
+            let (title, body) = summary(update);
+            let _ = app.notification()
+                .builder()
+                .title(title)
+                .body(body)
+                .show();
+            let _ = app.emit("post-match", update);
+            return;
+        }
+    });
+}
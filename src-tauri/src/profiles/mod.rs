@@ -0,0 +1,93 @@
+mod schedule;
+
+pub use schedule::ProfileScheduler;
+
+use tauri::AppHandle;
+use crate::config::{self, AppConfig, Profile, ProfileActivation};
+use crate::discord;
+
+fn new_id() -> String {
+    format!("{:016x}", rand::random::<u64>())
+}
+
+fn unique_name(cfg: &AppConfig, name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Profile name can't be empty".to_string());
+    }
+    if cfg.profiles.iter().any(|p| p.name.eq_ignore_ascii_case(name)) {
+        return Err(format!("A profile named \"{}\" already exists", name));
+    }
+    Ok(name.to_string())
+}
+
+pub fn create(name: &str) -> Result<AppConfig, String> {
+    config::update(|cfg| {
+        let name = unique_name(cfg, name)?;
+        let profile = cfg.snapshot(new_id(), name);
+        cfg.profiles.push(profile);
+        Ok(())
+    })
+}
+
+pub fn duplicate(id: &str, name: Option<&str>) -> Result<AppConfig, String> {
+    config::update(|cfg| {
+        let source = cfg.profiles.iter().find(|p| p.id == id).ok_or("Profile not found")?;
+        let name = unique_name(cfg, name.unwrap_or(&format!("{} copy", source.name)))?;
+        let mut profile = source.clone();
+        profile.id = new_id();
+        profile.name = name;
+        cfg.profiles.push(profile);
+        Ok(())
+    })
+}
+
+pub fn delete(id: &str) -> Result<AppConfig, String> {
+    config::update(|cfg| {
+        let before = cfg.profiles.len();
+        cfg.profiles.retain(|p| p.id != id);
+        if cfg.profiles.len() == before {
+            return Err("Profile not found".to_string());
+        }
+        if cfg.active_profile.as_deref() == Some(id) {
+            cfg.active_profile = None;
+        }
+        Ok(())
+    })
+}
+
+pub fn set_activation(id: &str, activation: ProfileActivation) -> Result<AppConfig, String> {
+    config::update(|cfg| {
+        let profile = cfg.profiles.iter_mut().find(|p| p.id == id).ok_or("Profile not found")?;
+        profile.activation = activation;
+        Ok(())
+    })
+}
+
+pub fn activate(app: &AppHandle, id: &str) -> Result<AppConfig, String> {
+    let cfg = config::update(|cfg| {
+        let profile = cfg.profiles.iter().find(|p| p.id == id).cloned().ok_or("Profile not found")?;
+        cfg.apply_profile(&profile);
+        Ok(())
+    })?;
+    if let Some(profile) = cfg.profiles.iter().find(|p| p.id == id) {
+        println!("[profiles] activated \"{}\"", profile.name);
+    }
+    config::notify(app, &cfg);
+    let rpc_cfg = cfg.discord.clone();
+    tauri::async_runtime::spawn_blocking(move || discord::sync(&rpc_cfg, None));
+    Ok(cfg)
+}
+
+pub fn restore(app: &AppHandle, settings: &Profile) -> Result<AppConfig, String> {
+    let cfg = config::update(|cfg| {
+        cfg.apply_profile(settings);
+        cfg.active_profile = None;
+        Ok(())
+    })?;
+    println!("[profiles] restored settings from before the scheduled switch");
+    config::notify(app, &cfg);
+    let rpc_cfg = cfg.discord.clone();
+    tauri::async_runtime::spawn_blocking(move || discord::sync(&rpc_cfg, None));
+    Ok(cfg)
+}
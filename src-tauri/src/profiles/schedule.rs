@@ -0,0 +1,312 @@
+use chrono::{DateTime, Datelike, Local, NaiveTime, Timelike, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use crate::config::{self, Profile, ProfileActivation, ScheduleWindow};
+use crate::valorant::api;
+use crate::valorant::connection::ValorantConnection;
+use crate::valorant::types::{ConnectionStatus, PartyState};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileSwitch {
+    pub from: Option<String>,
+    pub to: String,
+    pub name: String,
+    pub reason: String,
+}
+
+enum Restore {
+    Profile(String),
+    Settings(Box<Profile>),
+}
+
+#[derive(Default)]
+pub struct ProfileScheduler {
+    matched: Option<String>,
+    restore: Option<Restore>,
+}
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").ok()
+}
+
+fn local_time(timezone: &str, now: DateTime<Utc>) -> (Weekday, NaiveTime) {
+    match timezone.parse::<Tz>() {
+        Ok(tz) => {
+            let t = now.with_timezone(&tz);
+            (t.weekday(), t.time())
+        }
+        Err(_) => {
+            let t = now.with_timezone(&Local);
+            (t.weekday(), t.time())
+        }
+    }
+}
+
+fn on_day(window: &ScheduleWindow, day: Weekday) -> bool {
+    let name = day.to_string();
+    window.days.is_empty() || window.days.iter().any(|d| d.eq_ignore_ascii_case(&name))
+}
+
+fn in_window(window: &ScheduleWindow, day: Weekday, time: NaiveTime) -> bool {
+    let (Some(start), Some(end)) = (parse_time(&window.start), parse_time(&window.end)) else {
+        return false;
+    };
+    let time = time.with_second(0).unwrap_or(time);
+    if start <= end {
+        on_day(window, day) && start <= time && time < end
+    } else {
+        (on_day(window, day) && time >= start) || (on_day(window, day.pred()) && time < end)
+    }
+}
+
+fn schedule_reason(activation: &ProfileActivation, now: DateTime<Utc>) -> Option<String> {
+    let (day, time) = local_time(&activation.timezone, now);
+    activation.windows.iter()
+        .find(|w| in_window(w, day, time))
+        .map(|w| format!("scheduled {}–{}", w.start, w.end))
+}
+
+fn party_reason(activation: &ProfileActivation, party: Option<&PartyState>) -> Option<String> {
+    let party = party?;
+    let names: Vec<String> = party.members.iter()
+        .filter(|m| activation.party_with.iter().any(|want| {
+            want.eq_ignore_ascii_case(&m.puuid) || want.eq_ignore_ascii_case(&format!("{}#{}", m.game_name, m.tag_line))
+        }))
+        .map(|m| format!("{}#{}", m.game_name, m.tag_line))
+        .collect();
+    (!names.is_empty()).then(|| format!("in a party with {}", names.join(", ")))
+}
+
+fn matches(profile: &Profile, now: DateTime<Utc>, party: Option<&PartyState>) -> Option<String> {
+    let activation = &profile.activation;
+    if !activation.enabled || (activation.windows.is_empty() && activation.party_with.is_empty()) {
+        return None;
+    }
+    let mut reasons = Vec::new();
+    if !activation.windows.is_empty() {
+        reasons.push(schedule_reason(activation, now)?);
+    }
+    if !activation.party_with.is_empty() {
+        reasons.push(party_reason(activation, party)?);
+    }
+    Some(reasons.join(", "))
+}
+
+async fn fetch_party(conn: &ValorantConnection) -> Option<PartyState> {
+    let state = conn.get_state().await;
+    if state.status != ConnectionStatus::Connected {
+        return None;
+    }
+    let tokens = conn.get_tokens().await?;
+    api::fetch_party(&tokens, &state.player_info?.puuid, &state.region?, &state.shard?).await
+}
+
+impl ProfileScheduler {
+    fn switch(&self, app: &AppHandle, from: Option<String>, profile: &Profile, reason: String) {
+        let result = if profile.id.is_empty() {
+            super::restore(app, profile)
+        } else {
+            super::activate(app, &profile.id)
+        };
+        if let Err(e) = result {
+            println!("[profiles] failed to switch to \"{}\": {}", profile.name, e);
+            return;
+        }
+        println!("[profiles] switched to \"{}\" ({})", profile.name, reason);
+        let _ = app.emit("profile-switched", ProfileSwitch {
+            from,
+            to: profile.id.clone(),
+            name: profile.name.clone(),
+            reason,
+        });
+    }
+
+    pub async fn tick(&mut self, app: &AppHandle, conn: &ValorantConnection) {
+        let cfg = config::load();
+        let scheduled: Vec<&Profile> = cfg.profiles.iter().filter(|p| p.activation.enabled).collect();
+        if scheduled.is_empty() {
+            self.matched = None;
+            self.restore = None;
+            return;
+        }
+
+        let party = if scheduled.iter().any(|p| !p.activation.party_with.is_empty()) {
+            fetch_party(conn).await
+        } else {
+            None
+        };
+        let now = Utc::now();
+        let hit = scheduled.into_iter().find_map(|p| matches(p, now, party.as_ref()).map(|reason| (p, reason)));
+
+        let hit_id = hit.as_ref().map(|(p, _)| p.id.clone());
+        if hit_id == self.matched {
+            return;
+        }
+        let previous_hit = std::mem::replace(&mut self.matched, hit_id);
+
+        let active = cfg.active_profile.clone();
+        match hit {
+            Some((profile, reason)) => {
+                if active.as_deref() == Some(profile.id.as_str()) {
+                    return;
+                }
+                if self.restore.is_none() {
+                    self.restore = Some(match &active {
+                        Some(id) => Restore::Profile(id.clone()),
+                        None => Restore::Settings(Box::new(cfg.snapshot(String::new(), "previous settings".to_string()))),
+                    });
+                }
+                self.switch(app, active, profile, reason);
+            }
+            None => {
+                let Some(previous) = self.restore.take() else {
+                    return;
+                };
+                if active != previous_hit {
+                    return;
+                }
+                let reason = "scheduled profile ended".to_string();
+                match previous {
+                    Restore::Profile(id) => {
+                        if let Some(profile) = cfg.profiles.iter().find(|p| p.id == id) {
+                            self.switch(app, active, profile, reason);
+                        }
+                    }
+                    Restore::Settings(settings) => self.switch(app, active, &settings, reason),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::valorant::types::PartyMember;
+
+    fn window(days: &[&str], start: &str, end: &str) -> ScheduleWindow {
+        ScheduleWindow {
+            days: days.iter().map(|d| d.to_string()).collect(),
+            start: start.to_string(),
+            end: end.to_string(),
+        }
+    }
+
+    fn at(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn utc(y: i32, mo: u32, d: u32, h: u32, m: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, m, 0).unwrap()
+    }
+
+    fn profile(activation: ProfileActivation) -> Profile {
+        Profile { id: "p".to_string(), name: "P".to_string(), activation, ..Default::default() }
+    }
+
+    fn party(names: &[(&str, &str, &str)]) -> PartyState {
+        PartyState {
+            members: names.iter().map(|(puuid, name, tag)| PartyMember {
+                puuid: puuid.to_string(),
+                game_name: name.to_string(),
+                tag_line: tag.to_string(),
+                ..Default::default()
+            }).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn same_day_window_is_end_exclusive() {
+        let w = window(&["Mon"], "18:00", "23:00");
+        assert!(in_window(&w, Weekday::Mon, at(18, 0)));
+        assert!(in_window(&w, Weekday::Mon, at(22, 59)));
+        assert!(!in_window(&w, Weekday::Mon, at(23, 0)));
+        assert!(!in_window(&w, Weekday::Tue, at(19, 0)));
+    }
+
+    #[test]
+    fn window_crossing_midnight_belongs_to_its_start_day() {
+        let w = window(&["Fri"], "22:00", "02:00");
+        assert!(in_window(&w, Weekday::Fri, at(23, 30)));
+        assert!(in_window(&w, Weekday::Sat, at(1, 59)));
+        assert!(!in_window(&w, Weekday::Sat, at(2, 0)));
+        assert!(!in_window(&w, Weekday::Sat, at(23, 0)));
+        assert!(!in_window(&w, Weekday::Fri, at(1, 0)));
+    }
+
+    #[test]
+    fn window_without_days_applies_every_day() {
+        let w = window(&[], "22:00", "02:00");
+        assert!(in_window(&w, Weekday::Wed, at(1, 0)));
+        assert!(in_window(&w, Weekday::Sun, at(22, 0)));
+    }
+
+    #[test]
+    fn unparseable_window_never_matches() {
+        assert!(!in_window(&window(&[], "late", "02:00"), Weekday::Mon, at(1, 0)));
+    }
+
+    #[test]
+    fn local_time_follows_dst_in_named_timezone() {
+        assert_eq!(local_time("America/New_York", utc(2024, 3, 10, 6, 30)), (Weekday::Sun, at(1, 30)));
+        assert_eq!(local_time("America/New_York", utc(2024, 3, 10, 7, 30)), (Weekday::Sun, at(3, 30)));
+        assert_eq!(local_time("America/New_York", utc(2024, 11, 3, 5, 30)), (Weekday::Sun, at(1, 30)));
+        assert_eq!(local_time("America/New_York", utc(2024, 11, 3, 6, 30)), (Weekday::Sun, at(1, 30)));
+    }
+
+    #[test]
+    fn schedule_skips_the_missing_dst_hour() {
+        let activation = ProfileActivation {
+            enabled: true,
+            timezone: "America/New_York".to_string(),
+            windows: vec![window(&["Sun"], "02:00", "03:00")],
+            party_with: Vec::new(),
+        };
+        assert!(schedule_reason(&activation, utc(2024, 3, 10, 6, 59)).is_none());
+        assert!(schedule_reason(&activation, utc(2024, 3, 10, 7, 0)).is_none());
+        assert!(schedule_reason(&activation, utc(2024, 3, 17, 6, 30)).is_some());
+    }
+
+    #[test]
+    fn party_matches_by_riot_id_or_puuid() {
+        let activation = ProfileActivation {
+            party_with: vec!["friend#EUW".to_string(), "abc-123".to_string()],
+            ..Default::default()
+        };
+        let hit = party_reason(&activation, Some(&party(&[("x", "Friend", "euw"), ("abc-123", "Other", "NA1")])));
+        assert_eq!(hit.as_deref(), Some("in a party with Friend#euw, Other#NA1"));
+        assert!(party_reason(&activation, Some(&party(&[("y", "Someone", "EUW")]))).is_none());
+        assert!(party_reason(&activation, None).is_none());
+    }
+
+    #[test]
+    fn every_configured_condition_must_hold() {
+        let activation = ProfileActivation {
+            enabled: true,
+            timezone: "UTC".to_string(),
+            windows: vec![window(&[], "18:00", "23:00")],
+            party_with: vec!["friend#EUW".to_string()],
+        };
+        let with_friend = party(&[("x", "Friend", "EUW")]);
+        let evening = utc(2024, 5, 1, 19, 0);
+        let morning = utc(2024, 5, 1, 9, 0);
+
+        assert_eq!(
+            matches(&profile(activation.clone()), evening, Some(&with_friend)).as_deref(),
+            Some("scheduled 18:00–23:00, in a party with Friend#EUW"),
+        );
+        assert!(matches(&profile(activation.clone()), morning, Some(&with_friend)).is_none());
+        assert!(matches(&profile(activation.clone()), evening, None).is_none());
+        assert!(matches(&profile(ProfileActivation { enabled: false, ..activation }), evening, Some(&with_friend)).is_none());
+    }
+
+    #[test]
+    fn activation_without_conditions_never_matches() {
+        let activation = ProfileActivation { enabled: true, ..Default::default() };
+        assert!(matches(&profile(activation), utc(2024, 5, 1, 19, 0), None).is_none());
+    }
+}
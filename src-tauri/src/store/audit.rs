@@ -0,0 +1,74 @@
+use std::sync::Mutex;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use crate::config::AuditLogConfig;
+
+const STORE_NAME: &str = "audit";
+const ARCHIVE_NAME: &str = "audit_archive";
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+static LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub timestamp: u64,
+    pub feature: String,
+    pub action: String,
+    pub match_id: String,
+    #[serde(default)]
+    pub map_name: String,
+    #[serde(default)]
+    pub queue_id: String,
+    #[serde(default)]
+    pub rule: String,
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    #[serde(default)]
+    pub http_status: Option<u16>,
+    pub success: bool,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub delay_ms: u64,
+    #[serde(default)]
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub phase_remaining_ms: u64,
+}
+
+fn prune(entries: &mut Vec<AuditEntry>, cfg: &AuditLogConfig) -> Vec<AuditEntry> {
+    if cfg.retention_days > 0 {
+        let cutoff = super::now_ms().saturating_sub(cfg.retention_days as u64 * DAY_MS);
+        entries.retain(|e| e.timestamp >= cutoff);
+    }
+    let max = cfg.max_entries.max(1) as usize;
+    if entries.len() > max {
+        let excess = entries.len() - max;
+        return entries.drain(..excess).collect();
+    }
+    Vec::new()
+}
+
+pub fn append(entry: AuditEntry, cfg: &AuditLogConfig) -> Result<(), String> {
+    if !cfg.enabled {
+        return Ok(());
+    }
+    let _guard = LOCK.lock().unwrap();
+    let mut entries: Vec<AuditEntry> = super::load(STORE_NAME);
+    entries.push(entry);
+    let rotated = prune(&mut entries, cfg);
+    if !rotated.is_empty() {
+        let mut archive: Vec<AuditEntry> = super::load(ARCHIVE_NAME);
+        archive.extend(rotated);
+        prune(&mut archive, cfg);
+        super::save(ARCHIVE_NAME, &archive)?;
+    }
+    super::save(STORE_NAME, &entries)
+}
+
+pub fn recent(limit: usize) -> Vec<AuditEntry> {
+    let _guard = LOCK.lock().unwrap();
+    let entries: Vec<AuditEntry> = super::load(STORE_NAME);
+    entries.into_iter().rev().take(limit).collect()
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use crate::config::MapDodgeConfig;
+use crate::valorant::types::PenaltyRestrictions;
+
+const STORE_NAME: &str = "dodges";
+const MAX_RECORDS: usize = 200;
+const HOUR_MS: u64 = 60 * 60 * 1000;
+
+const PENALTY_WINDOW_MS: u64 = 24 * HOUR_MS;
+const PENALTY_TIERS: [(u32, u32); 3] = [(3, 3), (15, 10), (60, 10)];
+
+static LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DodgeRecord {
+    pub match_id: String,
+    pub timestamp: u64,
+    #[serde(default)]
+    pub map_name: String,
+    #[serde(default)]
+    pub queue_id: String,
+    pub automatic: bool,
+    #[serde(default)]
+    pub rule: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PenaltyEstimate {
+    pub dodges_24h: u32,
+    pub tier: u32,
+    pub next_timeout_minutes: u32,
+    pub next_rr_loss: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DodgeReport {
+    pub recent: Vec<DodgeRecord>,
+    pub estimate: PenaltyEstimate,
+    pub auto_dodges_in_window: u32,
+    pub max_auto_dodges: u32,
+    pub window_hours: u32,
+    pub restrictions: Option<PenaltyRestrictions>,
+}
+
+type DodgeStore = HashMap<String, Vec<DodgeRecord>>;
+
+pub fn record(puuid: &str, entry: DodgeRecord) -> Result<(), String> {
+    let _guard = LOCK.lock().unwrap();
+    let mut store: DodgeStore = super::load(STORE_NAME);
+    let records = store.entry(puuid.to_string()).or_default();
+    if records.iter().any(|r| r.match_id == entry.match_id) {
+        return Ok(());
+    }
+    records.push(entry);
+    if records.len() > MAX_RECORDS {
+        let excess = records.len() - MAX_RECORDS;
+        records.drain(..excess);
+    }
+    super::save(STORE_NAME, &store)
+}
+
+pub fn all(puuid: &str) -> Vec<DodgeRecord> {
+    let _guard = LOCK.lock().unwrap();
+    let mut store: DodgeStore = super::load(STORE_NAME);
+    store.remove(puuid).unwrap_or_default()
+}
+
+fn count_since(records: &[DodgeRecord], since: u64, automatic_only: bool) -> u32 {
+    records.iter()
+        .filter(|r| r.timestamp >= since && (!automatic_only || r.automatic))
+        .count() as u32
+}
+
+fn estimate(records: &[DodgeRecord]) -> PenaltyEstimate {
+    let dodges_24h = count_since(records, super::now_ms().saturating_sub(PENALTY_WINDOW_MS), false);
+    let next = PENALTY_TIERS[(dodges_24h as usize).min(PENALTY_TIERS.len() - 1)];
+    PenaltyEstimate {
+        dodges_24h,
+        tier: dodges_24h.min(PENALTY_TIERS.len() as u32),
+        next_timeout_minutes: next.0,
+        next_rr_loss: next.1,
+    }
+}
+
+pub fn cap_reached(puuid: &str, cfg: &MapDodgeConfig) -> Option<String> {
+    if cfg.max_auto_dodges == 0 {
+        return None;
+    }
+    let since = super::now_ms().saturating_sub(cfg.dodge_window_hours as u64 * HOUR_MS);
+    let count = count_since(&all(puuid), since, true);
+    (count >= cfg.max_auto_dodges).then(|| format!(
+        "Dodge cap reached — {} automatic dodge{} in the last {}h",
+        count,
+        if count == 1 { "" } else { "s" },
+        cfg.dodge_window_hours,
+    ))
+}
+
+pub fn report(puuid: &str, cfg: &MapDodgeConfig, restrictions: Option<PenaltyRestrictions>) -> DodgeReport {
+    let records = all(puuid);
+    let since = super::now_ms().saturating_sub(cfg.dodge_window_hours as u64 * HOUR_MS);
+    let mut recent: Vec<DodgeRecord> = records.iter().rev().take(20).cloned().collect();
+    recent.sort_by_key(|r| std::cmp::Reverse(r.timestamp));
+    DodgeReport {
+        estimate: estimate(&records),
+        auto_dodges_in_window: count_since(&records, since, true),
+        max_auto_dodges: cfg.max_auto_dodges,
+        window_hours: cfg.dodge_window_hours,
+        recent,
+        restrictions,
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use crate::export::match_result;
+use crate::summary::average_rank;
+use crate::valorant::types::{EncounterSummary, LiveMatch, LiveMatchPlayer};
+use super::history;
+
+const STORE_NAME: &str = "encounters";
+const MAX_LOBBIES: usize = 1000;
+
+static LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Lobby {
+    pub match_id: String,
+    pub timestamp: u64,
+    pub map_id: String,
+    pub queue_id: String,
+    pub allies: Vec<String>,
+    pub enemies: Vec<String>,
+    #[serde(default)]
+    pub ally_avg_rank: f32,
+    #[serde(default)]
+    pub enemy_avg_rank: f32,
+}
+
+type EncounterStore = HashMap<String, Vec<Lobby>>;
+
+pub fn record(puuid: &str, live: &LiveMatch) -> Vec<Lobby> {
+    let mut allies: Vec<String> = Vec::new();
+    let mut enemies: Vec<String> = Vec::new();
+    let my_team = live.ally_team.iter().find(|p| p.is_self).map(|p| p.team_id.clone()).unwrap_or_default();
+
+    for p in live.ally_team.iter().chain(live.enemy_team.iter()) {
+        if p.puuid == puuid || p.puuid.is_empty() {
+            continue;
+        }
+        if live.is_team_mode && p.team_id == my_team {
+            allies.push(p.puuid.clone());
+        } else {
+            enemies.push(p.puuid.clone());
+        }
+    }
+
+    let (ally_players, enemy_players): (Vec<&LiveMatchPlayer>, Vec<&LiveMatchPlayer>) = live.ally_team.iter()
+        .chain(live.enemy_team.iter())
+        .partition(|p| p.team_id == my_team);
+    let ally_avg_rank = average_rank(&ally_players);
+    let enemy_avg_rank = average_rank(&enemy_players);
+
+    let _guard = LOCK.lock().unwrap();
+    let mut store: EncounterStore = super::load(STORE_NAME);
+    let lobbies = store.entry(puuid.to_string()).or_default();
+
+    if let Some(existing) = lobbies.iter_mut().find(|l| l.match_id == live.match_id) {
+        if existing.allies.len() == allies.len() && existing.enemies.len() == enemies.len()
+            && existing.ally_avg_rank == ally_avg_rank && existing.enemy_avg_rank == enemy_avg_rank {
+            return lobbies.clone();
+        }
+        existing.allies = allies;
+        existing.enemies = enemies;
+        existing.ally_avg_rank = ally_avg_rank;
+        existing.enemy_avg_rank = enemy_avg_rank;
+    } else {
+        lobbies.push(Lobby {
+            match_id: live.match_id.clone(),
+            timestamp: super::now_ms(),
+            map_id: live.map_id.clone(),
+            queue_id: live.queue_id.clone(),
+            allies,
+            enemies,
+            ally_avg_rank,
+            enemy_avg_rank,
+        });
+        if lobbies.len() > MAX_LOBBIES {
+            let excess = lobbies.len() - MAX_LOBBIES;
+            lobbies.drain(..excess);
+        }
+    }
+
+    let lobbies = lobbies.clone();
+    if let Err(e) = super::save(STORE_NAME, &store) {
+        println!("[encounters] failed to save: {}", e);
+    }
+    lobbies
+}
+
+pub fn annotate(puuid: &str, live: &mut LiveMatch, lobbies: &[Lobby]) {
+    let results: HashMap<String, &'static str> = history::matches(puuid)
+        .iter()
+        .map(|m| (m.match_id.clone(), match_result(m)))
+        .collect();
+
+    for p in live.ally_team.iter_mut().chain(live.enemy_team.iter_mut()) {
+        if p.is_self {
+            continue;
+        }
+        p.encounters = summarize(&p.puuid, &live.match_id, lobbies, &results);
+    }
+}
+
+fn summarize(target: &str, current_match: &str, lobbies: &[Lobby], results: &HashMap<String, &'static str>) -> Option<EncounterSummary> {
+    let mut summary = EncounterSummary::default();
+
+    for lobby in lobbies.iter().filter(|l| l.match_id != current_match) {
+        let teammate = lobby.allies.iter().any(|p| p == target);
+        let enemy = lobby.enemies.iter().any(|p| p == target);
+        if !teammate && !enemy {
+            continue;
+        }
+
+        summary.times_seen += 1;
+        if teammate { summary.as_teammate += 1 } else { summary.as_enemy += 1 }
+        summary.last_seen = summary.last_seen.max(lobby.timestamp);
+
+        match results.get(&lobby.match_id) {
+            Some(&"win") => summary.wins += 1,
+            Some(&"loss") => summary.losses += 1,
+            Some(_) => summary.draws += 1,
+            None => {}
+        }
+    }
+
+    if summary.times_seen == 0 { None } else { Some(summary) }
+}
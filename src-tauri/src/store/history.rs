@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use once_cell::sync::Lazy;
+use crate::valorant::types::CompUpdate;
+
+const STORE_NAME: &str = "history";
+
+static LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+type HistoryStore = HashMap<String, Vec<CompUpdate>>;
+
+pub fn record(puuid: &str, updates: &[CompUpdate]) -> Result<(), String> {
+    if updates.is_empty() {
+        return Ok(());
+    }
+
+    let _guard = LOCK.lock().unwrap();
+    let mut store: HistoryStore = super::load(STORE_NAME);
+    let matches = store.entry(puuid.to_string()).or_default();
+
+    for update in updates {
+        match matches.iter_mut().find(|m| m.match_id == update.match_id) {
+            Some(existing) => *existing = update.clone(),
+            None => matches.push(update.clone()),
+        }
+    }
+    matches.sort_by_key(|m| std::cmp::Reverse(m.timestamp));
+
+    super::save(STORE_NAME, &store)
+}
+
+pub fn matches(puuid: &str) -> Vec<CompUpdate> {
+    let _guard = LOCK.lock().unwrap();
+    let mut store: HistoryStore = super::load(STORE_NAME);
+    store.remove(puuid).unwrap_or_default()
+}
@@ -0,0 +1,44 @@
+pub mod audit;
+pub mod dodges;
+pub mod encounters;
+pub mod history;
+pub mod watchlist;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::PathBuf;
+
+fn store_path(name: &str) -> PathBuf {
+    let exe = std::env::current_exe().unwrap_or_default();
+    exe.parent().unwrap_or(&PathBuf::from(".")).join(format!("downfall_{}.json", name))
+}
+
+pub fn load<T: DeserializeOwned + Default>(name: &str) -> T {
+    let path = store_path(name);
+    let Ok(data) = std::fs::read_to_string(&path) else {
+        return T::default();
+    };
+    match serde_json::from_str(&data) {
+        Ok(value) => value,
+        Err(e) => {
+            let backup = path.with_extension("json.bak");
+            println!("[store] failed to parse {}: {}, moving it to {}", path.display(), e, backup.display());
+            if let Err(e) = std::fs::rename(&path, &backup) {
+                println!("[store] failed to back up {}: {}", path.display(), e);
+            }
+            T::default()
+        }
+    }
+}
+
+pub fn save<T: Serialize>(name: &str, value: &T) -> Result<(), String> {
+    let data = serde_json::to_string(value).map_err(|e| e.to_string())?;
+    std::fs::write(store_path(name), data).map_err(|e| e.to_string())
+}
+
+pub fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
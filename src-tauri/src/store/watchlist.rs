@@ -0,0 +1,156 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+use crate::valorant::types::LiveMatch;
+
+const STORE_NAME: &str = "watchlist";
+
+static LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+static ALERTED: Lazy<Mutex<(String, HashSet<String>)>> =
+    Lazy::new(|| Mutex::new((String::new(), HashSet::new())));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchEntry {
+    pub puuid: String,
+    #[serde(default)]
+    pub game_name: String,
+    #[serde(default)]
+    pub tag_line: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub note: String,
+    #[serde(default)]
+    pub added_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchAlertPlayer {
+    pub puuid: String,
+    pub game_name: String,
+    pub tag_line: String,
+    pub tags: Vec<String>,
+    pub note: String,
+    pub is_enemy: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchAlert {
+    pub match_id: String,
+    pub phase: String,
+    pub map_name: String,
+    pub players: Vec<WatchAlertPlayer>,
+}
+
+type WatchlistStore = HashMap<String, WatchEntry>;
+
+pub fn all() -> Vec<WatchEntry> {
+    let _guard = LOCK.lock().unwrap();
+    let store: WatchlistStore = super::load(STORE_NAME);
+    let mut entries: Vec<WatchEntry> = store.into_values().collect();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.added_at));
+    entries
+}
+
+pub fn upsert(mut entry: WatchEntry) -> Result<WatchEntry, String> {
+    if entry.puuid.is_empty() {
+        return Err("missing puuid".to_string());
+    }
+    entry.tags = entry.tags.iter()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    let _guard = LOCK.lock().unwrap();
+    let mut store: WatchlistStore = super::load(STORE_NAME);
+    entry.added_at = store.get(&entry.puuid)
+        .map(|e| e.added_at)
+        .unwrap_or_else(super::now_ms);
+    store.insert(entry.puuid.clone(), entry.clone());
+    super::save(STORE_NAME, &store)?;
+    Ok(entry)
+}
+
+pub fn remove(puuid: &str) -> Result<(), String> {
+    let _guard = LOCK.lock().unwrap();
+    let mut store: WatchlistStore = super::load(STORE_NAME);
+    store.remove(puuid);
+    super::save(STORE_NAME, &store)
+}
+
+pub fn annotate(live: &mut LiveMatch) {
+    let store: WatchlistStore = {
+        let _guard = LOCK.lock().unwrap();
+        super::load(STORE_NAME)
+    };
+    for p in live.ally_team.iter_mut().chain(live.enemy_team.iter_mut()) {
+        if let Some(entry) = store.get(&p.puuid) {
+            p.watch_tags = entry.tags.clone();
+        }
+    }
+}
+
+pub fn alert(app: &AppHandle, live: &LiveMatch) {
+    let store: WatchlistStore = {
+        let _guard = LOCK.lock().unwrap();
+        super::load(STORE_NAME)
+    };
+
+    let fresh: Vec<WatchAlertPlayer> = {
+        let mut alerted = ALERTED.lock().unwrap();
+        if alerted.0 != live.match_id {
+            *alerted = (live.match_id.clone(), HashSet::new());
+        }
+
+        let ally_puuids: HashSet<&str> = live.ally_team.iter().map(|p| p.puuid.as_str()).collect();
+        live.ally_team.iter().chain(live.enemy_team.iter())
+            .filter(|p| !p.is_self)
+            .filter_map(|p| store.get(&p.puuid).map(|e| (p, e)))
+            .filter(|(p, _)| alerted.1.insert(p.puuid.clone()))
+            .map(|(p, e)| WatchAlertPlayer {
+                puuid: p.puuid.clone(),
+                game_name: if p.game_name.is_empty() { e.game_name.clone() } else { p.game_name.clone() },
+                tag_line: if p.tag_line.is_empty() { e.tag_line.clone() } else { p.tag_line.clone() },
+                tags: e.tags.clone(),
+                note: e.note.clone(),
+                is_enemy: live.is_team_mode && !ally_puuids.contains(p.puuid.as_str()),
+            })
+            .collect()
+    };
+
+    if fresh.is_empty() {
+        return;
+    }
+
+    let body = fresh.iter()
+        .map(|p| {
+            let side = if p.is_enemy { "enemy" } else { "team" };
+            if p.tags.is_empty() {
+                format!("{} ({})", p.game_name, side)
+            } else {
+                format!("{} ({}) — {}", p.game_name, side, p.tags.join(", "))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let _ = app.notification()
+        .builder()
+        .title("Watched player in your match")
+        .body(body)
+        .show();
+
+    let _ = app.emit("watchlist-alert", WatchAlert {
+        match_id: live.match_id.clone(),
+        phase: live.phase.clone(),
+        map_name: live.map_name.clone(),
+        players: fresh,
+    });
+}
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+use crate::export::match_result;
+use crate::store::encounters::Lobby;
+use crate::store::history;
+use crate::valorant::types::{LiveMatch, LiveMatchPlayer, TeamAggregate, TeamSummary};
+
+const DEFAULT_SCALE: f32 = 8.0;
+const MIN_CALIBRATION_MATCHES: usize = 10;
+
+fn ranked(players: &[&LiveMatchPlayer]) -> Vec<u32> {
+    let mut ranks: Vec<u32> = players.iter().map(|p| p.rank).filter(|r| *r > 0).collect();
+    ranks.sort_unstable();
+    ranks
+}
+
+pub fn average_rank(players: &[&LiveMatchPlayer]) -> f32 {
+    let ranks = ranked(players);
+    if ranks.is_empty() { 0.0 } else { ranks.iter().sum::<u32>() as f32 / ranks.len() as f32 }
+}
+
+fn aggregate(players: &[&LiveMatchPlayer]) -> TeamAggregate {
+    let ranks = ranked(players);
+    let median_rank = match ranks.len() {
+        0 => 0.0,
+        n if n % 2 == 0 => (ranks[n / 2 - 1] + ranks[n / 2]) as f32 / 2.0,
+        n => ranks[n / 2] as f32,
+    };
+
+    let peaks: Vec<u32> = players.iter().map(|p| p.peak_rank).filter(|r| *r > 0).collect();
+    let levels: Vec<u32> = players.iter().map(|p| p.account_level).filter(|l| *l > 0).collect();
+    let min_level = levels.iter().copied().min().unwrap_or(0);
+    let max_level = levels.iter().copied().max().unwrap_or(0);
+
+    TeamAggregate {
+        players: players.len() as u32,
+        ranked_players: ranks.len() as u32,
+        avg_rank: average_rank(players),
+        median_rank,
+        avg_peak_rank: if peaks.is_empty() { 0.0 } else { peaks.iter().sum::<u32>() as f32 / peaks.len() as f32 },
+        min_level,
+        max_level,
+        level_spread: max_level - min_level,
+    }
+}
+
+fn expected(diff: f32, scale: f32) -> f32 {
+    1.0 / (1.0 + 10f32.powf(-diff / scale))
+}
+
+fn calibrate(puuid: &str, current_match: &str, lobbies: &[Lobby]) -> (f32, u32) {
+    let results: HashMap<String, &'static str> = history::matches(puuid)
+        .iter()
+        .map(|m| (m.match_id.clone(), match_result(m)))
+        .collect();
+    fit_scale(&samples(current_match, lobbies, &results))
+}
+
+fn samples(current_match: &str, lobbies: &[Lobby], results: &HashMap<String, &'static str>) -> Vec<(f32, f32)> {
+    lobbies
+        .iter()
+        .filter(|l| l.match_id != current_match && l.ally_avg_rank > 0.0 && l.enemy_avg_rank > 0.0)
+        .filter_map(|l| {
+            let outcome = match *results.get(&l.match_id)? {
+                "win" => 1.0,
+                "loss" => 0.0,
+                _ => return None,
+            };
+            Some((l.ally_avg_rank - l.enemy_avg_rank, outcome))
+        })
+        .collect()
+}
+
+fn fit_scale(samples: &[(f32, f32)]) -> (f32, u32) {
+    if samples.len() < MIN_CALIBRATION_MATCHES {
+        return (DEFAULT_SCALE, samples.len() as u32);
+    }
+
+    let log_loss = |scale: f32| -> f32 {
+        samples.iter().map(|(diff, outcome)| {
+            let p = expected(*diff, scale).clamp(0.01, 0.99);
+            -(outcome * p.ln() + (1.0 - outcome) * (1.0 - p).ln())
+        }).sum()
+    };
+
+    let scale = (4..=80)
+        .map(|step| step as f32 * 0.5)
+        .min_by(|a, b| log_loss(*a).total_cmp(&log_loss(*b)))
+        .unwrap_or(DEFAULT_SCALE);
+
+    (scale, samples.len() as u32)
+}
+
+pub fn annotate(puuid: &str, live: &mut LiveMatch, lobbies: &[Lobby]) {
+    if !live.is_team_mode {
+        live.team_summary = None;
+        return;
+    }
+
+    let allies: Vec<&LiveMatchPlayer> = live.ally_team.iter().collect();
+    let enemies: Vec<&LiveMatchPlayer> = live.enemy_team.iter().collect();
+    let ally = aggregate(&allies);
+    let enemy = if enemies.is_empty() { None } else { Some(aggregate(&enemies)) };
+
+    let (scale, calibration_matches) = calibrate(puuid, &live.match_id, lobbies);
+    let win_probability = enemy.as_ref()
+        .filter(|e| ally.ranked_players > 0 && e.ranked_players > 0)
+        .map(|e| expected(ally.avg_rank - e.avg_rank, scale) * 100.0);
+
+    live.team_summary = Some(TeamSummary {
+        ally,
+        enemy,
+        win_probability,
+        calibration_matches,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(rank: u32, peak_rank: u32, account_level: u32) -> LiveMatchPlayer {
+        LiveMatchPlayer { rank, peak_rank, account_level, ..Default::default() }
+    }
+
+    fn lobby(match_id: &str, ally_avg_rank: f32, enemy_avg_rank: f32) -> Lobby {
+        Lobby {
+            match_id: match_id.to_string(),
+            timestamp: 0,
+            map_id: String::new(),
+            queue_id: String::new(),
+            allies: Vec::new(),
+            enemies: Vec::new(),
+            ally_avg_rank,
+            enemy_avg_rank,
+        }
+    }
+
+    fn decisive(n: usize) -> Vec<(f32, f32)> {
+        (0..n).map(|i| if i % 2 == 0 { (2.0, 1.0) } else { (-2.0, 0.0) }).collect()
+    }
+
+    #[test]
+    fn aggregate_skips_unranked_players() {
+        let players = [player(0, 0, 0), player(10, 12, 50), player(14, 15, 150), player(12, 0, 100)];
+        let refs: Vec<&LiveMatchPlayer> = players.iter().collect();
+        let agg = aggregate(&refs);
+        assert_eq!(agg.players, 4);
+        assert_eq!(agg.ranked_players, 3);
+        assert_eq!(agg.avg_rank, 12.0);
+        assert_eq!(agg.median_rank, 12.0);
+        assert_eq!(agg.avg_peak_rank, 13.5);
+        assert_eq!((agg.min_level, agg.max_level, agg.level_spread), (50, 150, 100));
+    }
+
+    #[test]
+    fn median_of_even_count_averages_middle_ranks() {
+        let players = [player(10, 0, 0), player(11, 0, 0), player(14, 0, 0), player(20, 0, 0)];
+        let refs: Vec<&LiveMatchPlayer> = players.iter().collect();
+        assert_eq!(aggregate(&refs).median_rank, 12.5);
+    }
+
+    #[test]
+    fn expected_is_even_for_equal_teams() {
+        assert_eq!(expected(0.0, DEFAULT_SCALE), 0.5);
+        assert!(expected(3.0, DEFAULT_SCALE) > 0.5);
+        assert!(expected(-3.0, DEFAULT_SCALE) < 0.5);
+    }
+
+    #[test]
+    fn samples_skip_current_unranked_and_drawn_matches() {
+        let lobbies = [
+            lobby("current", 12.0, 10.0),
+            lobby("unranked", 0.0, 10.0),
+            lobby("draw", 12.0, 10.0),
+            lobby("unknown", 12.0, 10.0),
+            lobby("win", 12.0, 10.0),
+            lobby("loss", 9.0, 10.0),
+        ];
+        let results: HashMap<String, &'static str> = [
+            ("current", "win"), ("unranked", "win"), ("draw", "draw"), ("win", "win"), ("loss", "loss"),
+        ].into_iter().map(|(id, r)| (id.to_string(), r)).collect();
+
+        assert_eq!(samples("current", &lobbies, &results), vec![(2.0, 1.0), (-1.0, 0.0)]);
+    }
+
+    #[test]
+    fn calibration_uses_default_below_minimum() {
+        let (scale, matches) = fit_scale(&decisive(MIN_CALIBRATION_MATCHES - 1));
+        assert_eq!(scale, DEFAULT_SCALE);
+        assert_eq!(matches, (MIN_CALIBRATION_MATCHES - 1) as u32);
+    }
+
+    #[test]
+    fn calibration_fits_scale_at_minimum() {
+        let (scale, matches) = fit_scale(&decisive(MIN_CALIBRATION_MATCHES));
+        assert_eq!(matches, MIN_CALIBRATION_MATCHES as u32);
+        assert!(scale < DEFAULT_SCALE);
+        assert!(scale >= 2.0);
+    }
+}
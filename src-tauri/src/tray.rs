@@ -0,0 +1,279 @@
+use std::sync::{Arc, Mutex};
+use image::{Rgba, RgbaImage};
+use once_cell::sync::{Lazy, OnceCell};
+use tauri::image::Image;
+use tauri::menu::{CheckMenuItemBuilder, Menu, MenuBuilder, MenuEvent, MenuItemBuilder, SubmenuBuilder};
+use tauri::tray::{MouseButton, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+use crate::automation;
+use crate::config::{self, AppConfig};
+use crate::discord;
+use crate::profiles;
+use crate::valorant::connection::ValorantConnection;
+use crate::valorant::phase::{MatchPhase, PhaseSnapshot};
+use crate::valorant::types::{ConnectionState, ConnectionStatus, LivePresence};
+
+pub const TRAY_ID: &str = "main";
+const PROFILE_PREFIX: &str = "profile:";
+
+static STATUS: Lazy<Mutex<(ConnectionStatus, String)>> =
+    Lazy::new(|| Mutex::new((ConnectionStatus::Connecting, "Connecting to Valorant...".to_string())));
+static BASE_ICON: OnceCell<RgbaImage> = OnceCell::new();
+static SHOWN: Lazy<Mutex<Option<(Badge, String)>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Badge {
+    Offline,
+    Menus,
+    Queueing,
+    Pregame,
+    InGame,
+}
+
+impl Badge {
+    fn color(self) -> Rgba<u8> {
+        match self {
+            Badge::Offline => Rgba([110, 110, 120, 255]),
+            Badge::Menus => Rgba([64, 192, 96, 255]),
+            Badge::Queueing => Rgba([224, 160, 64, 255]),
+            Badge::Pregame => Rgba([64, 140, 224, 255]),
+            Badge::InGame => Rgba([224, 64, 80, 255]),
+        }
+    }
+}
+
+fn describe(state: &ConnectionState, phase: &PhaseSnapshot, own: Option<&LivePresence>) -> (Badge, String) {
+    if state.status != ConnectionStatus::Connected {
+        return (Badge::Offline, status_label(state));
+    }
+    let on_map = |prefix: &str| {
+        if phase.map_name.is_empty() { prefix.to_string() } else { format!("{} on {}", prefix, phase.map_name) }
+    };
+    match phase.phase {
+        MatchPhase::Menus | MatchPhase::PostGame => (Badge::Menus, "In menus".to_string()),
+        MatchPhase::Queueing => (Badge::Queueing, "Queueing".to_string()),
+        MatchPhase::MatchFound => (Badge::Queueing, "Match found".to_string()),
+        MatchPhase::Pregame => (Badge::Pregame, on_map("Pregame")),
+        MatchPhase::InGame => {
+            let live = own.filter(|p| p.session_loop_state.eq_ignore_ascii_case("INGAME"));
+            match live {
+                Some(p) => (Badge::InGame, on_map(&format!("In-game {}–{}", p.ally_score, p.enemy_score))),
+                None => (Badge::InGame, on_map("In-game")),
+            }
+        }
+    }
+}
+
+fn blend(dst: &mut Rgba<u8>, color: Rgba<u8>, coverage: f32) {
+    for c in 0..3 {
+        dst[c] = (dst[c] as f32 * (1.0 - coverage) + color[c] as f32 * coverage) as u8;
+    }
+    dst[3] = dst[3].max((coverage * 255.0) as u8);
+}
+
+fn with_badge(base: &RgbaImage, color: Rgba<u8>) -> RgbaImage {
+    let mut img = base.clone();
+    let size = img.width().min(img.height()) as f32;
+    let radius = size * 0.2;
+    let outline = radius + size * 0.05;
+    let cx = img.width() as f32 - outline;
+    let cy = img.height() as f32 - outline;
+
+    for (x, y, px) in img.enumerate_pixels_mut() {
+        let d = ((x as f32 + 0.5 - cx).powi(2) + (y as f32 + 0.5 - cy).powi(2)).sqrt();
+        let outline_coverage = (outline + 0.5 - d).clamp(0.0, 1.0);
+        if outline_coverage == 0.0 {
+            continue;
+        }
+        blend(px, Rgba([15, 15, 20, 255]), outline_coverage);
+        blend(px, color, (radius + 0.5 - d).clamp(0.0, 1.0));
+    }
+    img
+}
+
+pub fn update(app: &AppHandle, state: &ConnectionState, phase: &PhaseSnapshot, own: Option<&LivePresence>) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let (badge, text) = describe(state, phase, own);
+    let mut shown = SHOWN.lock().unwrap();
+    let badge_changed = shown.as_ref().map(|(b, _)| *b) != Some(badge);
+    if !badge_changed && shown.as_ref().is_some_and(|(_, t)| *t == text) {
+        return;
+    }
+
+    let _ = tray.set_tooltip(Some(format!("Downfall — {}", text)));
+    if badge_changed {
+        if let Some(base) = BASE_ICON.get() {
+            let icon = with_badge(base, badge.color());
+            let (w, h) = icon.dimensions();
+            let _ = tray.set_icon(Some(Image::new_owned(icon.into_raw(), w, h)));
+        }
+    }
+    *shown = Some((badge, text));
+}
+
+fn status_label(state: &ConnectionState) -> String {
+    match (&state.status, &state.player_info) {
+        (ConnectionStatus::Connected, Some(info)) => format!("Connected as {}#{}", info.game_name, info.tag_line),
+        (ConnectionStatus::Connected, None) => "Connected".to_string(),
+        (ConnectionStatus::Connecting, _) => "Connecting to Valorant...".to_string(),
+        (ConnectionStatus::Disconnected, _) => "Valorant not running".to_string(),
+    }
+}
+
+fn build_menu(app: &AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let cfg = config::load();
+    let (status, label) = STATUS.lock().unwrap().clone();
+    let connected = status == ConnectionStatus::Connected;
+
+    let status_item = MenuItemBuilder::with_id("status", label).enabled(false).build(app)?;
+    let instalock = CheckMenuItemBuilder::new("Instalock").id("instalock").checked(cfg.instalock.active).build(app)?;
+    let map_dodge = CheckMenuItemBuilder::new("Map Dodge").id("map_dodge").checked(cfg.map_dodge.active).build(app)?;
+    let discord_rpc = CheckMenuItemBuilder::new("Discord RPC").id("discord").checked(cfg.discord.enabled).build(app)?;
+
+    let mut profile_menu = SubmenuBuilder::new(app, "Profile").enabled(!cfg.profiles.is_empty());
+    for profile in &cfg.profiles {
+        let item = CheckMenuItemBuilder::new(&profile.name)
+            .id(format!("{}{}", PROFILE_PREFIX, profile.id))
+            .checked(cfg.active_profile.as_deref() == Some(profile.id.as_str()))
+            .build(app)?;
+        profile_menu = profile_menu.item(&item);
+    }
+    let profile_menu = profile_menu.build()?;
+
+    let dodge = MenuItemBuilder::with_id("dodge", "Dodge Current Pregame").enabled(connected).build(app)?;
+    let live_match = MenuItemBuilder::with_id("live_match", "Open Live Match").build(app)?;
+
+    MenuBuilder::new(app)
+        .item(&status_item)
+        .separator()
+        .item(&instalock)
+        .item(&map_dodge)
+        .item(&discord_rpc)
+        .item(&profile_menu)
+        .separator()
+        .item(&dodge)
+        .item(&live_match)
+        .separator()
+        .text("show", "Show Downfall")
+        .text("quit", "Quit")
+        .build()
+}
+
+pub fn sync(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    match build_menu(app) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => println!("[tray] failed to build menu: {}", e),
+    }
+}
+
+pub fn set_status(app: &AppHandle, state: &ConnectionState) {
+    let next = (state.status.clone(), status_label(state));
+    {
+        let mut status = STATUS.lock().unwrap();
+        if *status == next {
+            return;
+        }
+        *status = next;
+    }
+    sync(app);
+}
+
+fn show_window(app: &AppHandle) {
+    if let Some(w) = app.get_webview_window("main") {
+        let _ = w.show();
+        let _ = w.unminimize();
+        let _ = w.set_focus();
+    }
+}
+
+fn notify(app: &AppHandle, body: &str) {
+    let _ = app.notification().builder().title("Downfall").body(body).show();
+}
+
+fn toggle(app: &AppHandle, apply: impl FnOnce(&mut AppConfig)) -> Option<AppConfig> {
+    match config::update(|cfg| {
+        apply(cfg);
+        Ok(())
+    }) {
+        Ok(cfg) => {
+            config::notify(app, &cfg);
+            Some(cfg)
+        }
+        Err(e) => {
+            println!("[tray] failed to save config: {}", e);
+            sync(app);
+            None
+        }
+    }
+}
+
+fn dodge_current(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let conn = app.state::<Arc<ValorantConnection>>().inner().clone();
+        match automation::manual_dodge(&conn, None).await {
+            Ok(map) if map.is_empty() => notify(&app, "Dodged the current match"),
+            Ok(map) => notify(&app, &format!("Dodged {}", map)),
+            Err(e) => notify(&app, &format!("Couldn't dodge: {}", e)),
+        }
+    });
+}
+
+fn on_menu_event(app: &AppHandle, event: MenuEvent) {
+    match event.id().as_ref() {
+        "instalock" => {
+            toggle(app, |cfg| cfg.instalock.active = !cfg.instalock.active);
+        }
+        "map_dodge" => {
+            toggle(app, |cfg| cfg.map_dodge.active = !cfg.map_dodge.active);
+        }
+        "discord" => {
+            if let Some(cfg) = toggle(app, |cfg| cfg.discord.enabled = !cfg.discord.enabled) {
+                tauri::async_runtime::spawn_blocking(move || discord::sync(&cfg.discord, None));
+            }
+        }
+        "dodge" => dodge_current(app),
+        "live_match" => {
+            show_window(app);
+            let _ = app.emit("navigate", "livematch");
+        }
+        "show" => show_window(app),
+        "quit" => app.exit(0),
+        id => {
+            if let Some(profile_id) = id.strip_prefix(PROFILE_PREFIX) {
+                if let Err(e) = profiles::activate(app, profile_id) {
+                    println!("[tray] failed to activate profile: {}", e);
+                    sync(app);
+                }
+            }
+        }
+    }
+}
+
+pub fn create(app: &AppHandle, base: RgbaImage) -> tauri::Result<()> {
+    let (w, h) = base.dimensions();
+    let icon = Image::new_owned(base.clone().into_raw(), w, h);
+    let _ = BASE_ICON.set(base);
+    let menu = build_menu(app)?;
+    TrayIconBuilder::with_id(TRAY_ID)
+        .icon(icon)
+        .tooltip("Downfall")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(on_menu_event)
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click { button: MouseButton::Left, .. } = event {
+                show_window(tray.app_handle());
+            }
+        })
+        .build(app)?;
+    Ok(())
+}
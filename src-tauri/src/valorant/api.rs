@@ -2,7 +2,9 @@ use base64::Engine;
 use base64::engine::general_purpose::STANDARD as BASE64;
 use reqwest::Client;
 use serde_json::Value;
-use super::types::{Lockfile, PlayerInfo, RegionInfo, AuthTokens, AccountXP, PlayerMMR, CompUpdate, AgentInfo, PregameState, CurrentMatch, LiveMatch, LiveMatchPlayer, PartyState, PartyMember, PartyInvite, Friend};
+use super::ratelimit::PD_LIMITER;
+use super::types::{Lockfile, PlayerInfo, RegionInfo, AuthTokens, AccountXP, PlayerMMR, CompUpdate, AgentInfo, PregameState, PregamePlayer, CurrentMatch, MatchPlayer, LiveMatch, LiveMatchPlayer, PartyState, PartyMember, PartyInvite, Friend, RecentForm, MatchLoadouts, PlayerLoadout, WeaponLoadout, LivePresence, PenaltyRestrictions};
+use super::content::{self, ContentCatalog};
 
 pub fn build_client(lock: &Lockfile) -> Result<Client, reqwest::Error> {
     let auth = BASE64.encode(format!("riot:{}", lock.password));
@@ -208,6 +210,7 @@ pub async fn fetch_comp_updates(tokens: &AuthTokens, puuid: &str, shard: &str) -
         "/mmr/v1/players/{}/competitiveupdates?startIndex=0&endIndex=15",
         puuid
     ));
+    PD_LIMITER.acquire().await;
     let resp: Value = match client.get(&url).send().await {
         Ok(r) => match r.json().await { Ok(v) => v, Err(_) => return Vec::new() },
         Err(_) => return Vec::new(),
@@ -226,15 +229,10 @@ pub async fn fetch_comp_updates(tokens: &AuthTokens, puuid: &str, shard: &str) -
         .take(10)
         .collect();
 
-    let futs: Vec<_> = entries.iter()
-        .map(|(mid, _)| fetch_match_kda(&client, shard, mid, puuid))
-        .collect();
-
-    let results: Vec<_> = futures::future::join_all(futs).await;
-
     let mut updates: Vec<CompUpdate> = Vec::new();
-    for (i, (match_id, m)) in entries.iter().enumerate() {
-        let (kills, deaths, assists, score, rounds_won, rounds_lost) = results[i];
+    for (match_id, m) in &entries {
+        PD_LIMITER.acquire().await;
+        let stats = fetch_match_kda(&client, shard, match_id, puuid).await;
 
         let rr_before = m["RankedRatingBeforeUpdate"].as_i64().unwrap_or(0);
         let rr_after = m["RankedRatingAfterUpdate"].as_i64().unwrap_or(0);
@@ -252,39 +250,53 @@ pub async fn fetch_comp_updates(tokens: &AuthTokens, puuid: &str, shard: &str) -
         updates.push(CompUpdate {
             match_id: match_id.clone(),
             map_id: m["MapID"].as_str().unwrap_or_default().to_string(),
+            agent_id: stats.agent_id.clone(),
+            queue_id: stats.queue_id.clone(),
             rank_before,
             rank_after,
             rr_before: rr_before as u32,
             rr_after: rr_after as u32,
             rr_change,
             timestamp: m["MatchStartTime"].as_u64().unwrap_or(0),
-            kills,
-            deaths,
-            assists,
-            score,
-            rounds_won,
-            rounds_lost,
+            kills: stats.kills,
+            deaths: stats.deaths,
+            assists: stats.assists,
+            score: stats.score,
+            rounds_won: stats.rounds_won,
+            rounds_lost: stats.rounds_lost,
         });
     }
 
     updates
 }
 
-async fn fetch_match_kda(client: &Client, shard: &str, match_id: &str, puuid: &str) -> (u32, u32, u32, u32, u32, u32) {
+#[derive(Default)]
+struct MatchStats {
+    kills: u32,
+    deaths: u32,
+    assists: u32,
+    score: u32,
+    rounds_won: u32,
+    rounds_lost: u32,
+    agent_id: String,
+    queue_id: String,
+}
+
+async fn fetch_match_kda(client: &Client, shard: &str, match_id: &str, puuid: &str) -> MatchStats {
     let url = pd_url(shard, &format!("/match-details/v1/matches/{}", match_id));
     let resp: Value = match client.get(&url).send().await {
-        Ok(r) => match r.json().await { Ok(v) => v, Err(_) => return (0, 0, 0, 0, 0, 0) },
-        Err(_) => return (0, 0, 0, 0, 0, 0),
+        Ok(r) => match r.json().await { Ok(v) => v, Err(_) => return MatchStats::default() },
+        Err(_) => return MatchStats::default(),
     };
 
     let players = match resp["players"].as_array() {
         Some(p) => p,
-        None => return (0, 0, 0, 0, 0, 0),
+        None => return MatchStats::default(),
     };
 
     let player = match players.iter().find(|p| p["subject"].as_str() == Some(puuid)) {
         Some(p) => p,
-        None => return (0, 0, 0, 0, 0, 0),
+        None => return MatchStats::default(),
     };
 
     let stats = &player["stats"];
@@ -308,7 +320,103 @@ async fn fetch_match_kda(client: &Client, shard: &str, match_id: &str, puuid: &s
         }
     }
 
-    (kills, deaths, assists, score, rounds_won, rounds_lost)
+    let agent_id = player["characterId"].as_str().unwrap_or_default().to_lowercase();
+    let queue_id = resp["matchInfo"]["queueID"].as_str().unwrap_or_default().to_string();
+
+    MatchStats { kills, deaths, assists, score, rounds_won, rounds_lost, agent_id, queue_id }
+}
+
+pub async fn fetch_recent_form(tokens: &AuthTokens, puuid: &str, shard: &str, queue_id: &str, count: usize, agents: &std::collections::HashMap<String, (String, String)>) -> Option<RecentForm> {
+    let client = pd_client(tokens)?;
+
+    let queue = if queue_id.is_empty() { String::new() } else { format!("&queue={}", queue_id) };
+    let history_url = pd_url(shard, &format!("/match-history/v1/history/{}?startIndex=0&endIndex={}{}", puuid, count, queue));
+    PD_LIMITER.acquire().await;
+    let history: Value = client.get(&history_url).send().await.ok()?.json().await.ok()?;
+    let match_ids: Vec<String> = history["History"].as_array()?
+        .iter()
+        .filter_map(|m| m["MatchID"].as_str().map(|s| s.to_string()))
+        .take(count)
+        .collect();
+
+    let mut form = RecentForm::default();
+    let mut kills: u32 = 0;
+    let mut deaths: u32 = 0;
+    let mut headshots: u32 = 0;
+    let mut shots: u32 = 0;
+    let mut agent_counts: Vec<(String, u32)> = Vec::new();
+
+    for match_id in &match_ids {
+        PD_LIMITER.acquire().await;
+        let url = pd_url(shard, &format!("/match-details/v1/matches/{}", match_id));
+        let details: Value = match client.get(&url).send().await {
+            Ok(r) if r.status().is_success() => match r.json().await { Ok(v) => v, Err(_) => continue },
+            _ => continue,
+        };
+
+        let Some(player) = details["players"].as_array()
+            .and_then(|players| players.iter().find(|p| p["subject"].as_str() == Some(puuid)))
+        else { continue };
+
+        form.matches += 1;
+        kills += player["stats"]["kills"].as_u64().unwrap_or(0) as u32;
+        deaths += player["stats"]["deaths"].as_u64().unwrap_or(0) as u32;
+
+        let team_id = player["teamId"].as_str().unwrap_or_default();
+        let teams = details["teams"].as_array().map(Vec::as_slice).unwrap_or_default();
+        let won = teams.iter()
+            .find(|t| t["teamId"].as_str() == Some(team_id))
+            .and_then(|t| t["won"].as_bool());
+        let decided = teams.iter().any(|t| t["won"].as_bool() == Some(true));
+        match won {
+            Some(true) => form.wins += 1,
+            Some(false) if decided => form.losses += 1,
+            _ => {}
+        }
+
+        let agent_id = player["characterId"].as_str().unwrap_or_default().to_lowercase();
+        match agent_counts.iter_mut().find(|(id, _)| *id == agent_id) {
+            Some((_, n)) => *n += 1,
+            None => agent_counts.push((agent_id, 1)),
+        }
+
+        for round in details["roundResults"].as_array().into_iter().flatten() {
+            let stats = round["playerStats"].as_array()
+                .and_then(|ps| ps.iter().find(|p| p["subject"].as_str() == Some(puuid)));
+            for dmg in stats.and_then(|s| s["damage"].as_array()).into_iter().flatten() {
+                let head = dmg["headshots"].as_u64().unwrap_or(0) as u32;
+                headshots += head;
+                shots += head + dmg["bodyshots"].as_u64().unwrap_or(0) as u32 + dmg["legshots"].as_u64().unwrap_or(0) as u32;
+            }
+        }
+    }
+
+    if form.matches == 0 {
+        return None;
+    }
+
+    let decided = form.wins + form.losses;
+    form.win_rate = if decided == 0 { 0.0 } else { form.wins as f32 / decided as f32 * 100.0 };
+    form.kd = if deaths == 0 { kills as f32 } else { kills as f32 / deaths as f32 };
+    form.hs_percent = if shots == 0 { 0.0 } else { headshots as f32 / shots as f32 * 100.0 };
+
+    agent_counts.sort_by_key(|(_, n)| std::cmp::Reverse(*n));
+    form.top_agents = agent_counts.iter()
+        .take(3)
+        .map(|(id, _)| agents.get(id).map(|(name, _)| name.clone()).unwrap_or_else(|| id.clone()))
+        .collect();
+
+    let updates_url = pd_url(shard, &format!("/mmr/v1/players/{}/competitiveupdates?startIndex=0&endIndex={}&queue=competitive", puuid, count));
+    PD_LIMITER.acquire().await;
+    if let Ok(resp) = client.get(&updates_url).send().await {
+        if let Ok(updates) = resp.json::<Value>().await {
+            form.rr_trend = updates["Matches"].as_array()
+                .map(|arr| arr.iter().filter_map(|m| m["RankedRatingEarned"].as_i64()).map(|rr| rr as i32).collect())
+                .unwrap_or_default();
+        }
+    }
+
+    Some(form)
 }
 
 const AGENT_ENTITLEMENT_TYPE: &str = "01bb38e1-da47-4e6a-9b3d-945fe4655707";
@@ -360,7 +468,7 @@ pub async fn fetch_agents(tokens: &AuthTokens, puuid: &str, shard: &str) -> Vec<
     result
 }
 
-pub async fn fetch_current_match(client: &Client, lock: &Lockfile) -> Result<Option<CurrentMatch>, Box<dyn std::error::Error + Send + Sync>> {
+pub async fn fetch_current_match(client: &Client, lock: &Lockfile, tokens: &AuthTokens, region: &str, shard: &str) -> Result<Option<CurrentMatch>, Box<dyn std::error::Error + Send + Sync>> {
     let session_url = format!("{}/chat/v1/session", base_url(lock));
     let session: Value = client.get(&session_url).send().await?.json().await?;
     let puuid = session["puuid"].as_str().unwrap_or_default();
@@ -369,43 +477,116 @@ pub async fn fetch_current_match(client: &Client, lock: &Lockfile) -> Result<Opt
         return Ok(None);
     }
 
-    let presences_url = format!("{}/chat/v4/presences", base_url(lock));
-    let presences: Value = client.get(&presences_url).send().await?.json().await?;
-
-    let friends = presences["presences"].as_array();
-    if friends.is_none() {
-        return Ok(None);
+    let glz = glz_client(tokens).ok_or("no glz client")?;
+    let mut phase = "pregame";
+    let mut match_id = None;
+    for base in ["pregame", "core-game"] {
+        let player_url = glz_url(region, shard, &format!("/{}/v1/players/{}", base, puuid));
+        let resp = glz.get(&player_url).send().await?;
+        if !resp.status().is_success() {
+            continue;
+        }
+        let player: Value = resp.json().await?;
+        if let Some(id) = player["MatchID"].as_str().filter(|s| !s.is_empty()) {
+            match_id = Some(id.to_string());
+            phase = if base == "pregame" { "pregame" } else { "ingame" };
+            break;
+        }
     }
-
-    let self_presence = friends.unwrap().iter().find(|p| p["puuid"].as_str() == Some(puuid));
-    if self_presence.is_none() {
+    let Some(match_id) = match_id else {
         return Ok(None);
-    }
+    };
 
-    let private_b64 = self_presence.unwrap()["private"].as_str().unwrap_or_default();
-    if private_b64.is_empty() {
-        return Ok(None);
-    }
+    let base = if phase == "pregame" { "pregame" } else { "core-game" };
+    let match_url = glz_url(region, shard, &format!("/{}/v1/matches/{}", base, match_id));
+    let match_data: Value = glz.get(&match_url).send().await?.json().await?;
 
-    let decoded = BASE64.decode(private_b64)?;
-    let private: Value = serde_json::from_slice(&decoded)?;
+    let presence = fetch_self_presence(client, lock, puuid).await.unwrap_or_default();
+    let presence_queue = presence["queueId"].as_str()
+        .filter(|s| !s.is_empty())
+        .or_else(|| presence["matchPresenceData"]["queueId"].as_str())
+        .unwrap_or_default();
+    let provisioning_flow = presence["provisioningFlow"].as_str()
+        .filter(|s| !s.is_empty())
+        .or_else(|| presence["matchPresenceData"]["provisioningFlow"].as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let queue_id = match_data["QueueID"].as_str()
+        .or_else(|| match_data["MatchmakingData"]["QueueID"].as_str())
+        .filter(|s| !s.is_empty())
+        .unwrap_or(presence_queue)
+        .to_string();
+    let is_ranked = match_data["IsRanked"].as_bool()
+        .or_else(|| match_data["MatchmakingData"]["IsRanked"].as_bool())
+        .unwrap_or(queue_id == "competitive" || queue_id == "premier");
+    let map_id = match_data["MapID"].as_str().unwrap_or_default().to_string();
+
+    let roster: Vec<(&Value, String)> = if phase == "pregame" {
+        let team_id = match_data["AllyTeam"]["TeamID"].as_str().unwrap_or_default().to_string();
+        match_data["AllyTeam"]["Players"].as_array()
+            .map(|arr| arr.iter().map(|p| (p, team_id.clone())).collect())
+            .unwrap_or_default()
+    } else {
+        match_data["Players"].as_array()
+            .map(|arr| arr.iter().map(|p| (p, p["TeamID"].as_str().unwrap_or_default().to_string())).collect())
+            .unwrap_or_default()
+    };
 
-    let match_id = private["matchMap"].as_str().unwrap_or_default();
-    if match_id.is_empty() {
-        return Ok(None);
-    }
+    let puuids: Vec<String> = roster.iter()
+        .filter_map(|(p, _)| p["Subject"].as_str().map(|s| s.to_string()))
+        .collect();
+    let names = match pd_client(tokens) {
+        Some(pd) => fetch_names(&pd, shard, &puuids).await,
+        None => std::collections::HashMap::new(),
+    };
+
+    let players = roster.iter().map(|(p, team_id)| {
+        let pid = p["Subject"].as_str().unwrap_or_default().to_string();
+        let (game_name, tag_line) = names.get(&pid).cloned().unwrap_or_default();
+        MatchPlayer {
+            puuid: pid,
+            game_name,
+            tag_line,
+            team_id: team_id.clone(),
+            character_id: p["CharacterID"].as_str().unwrap_or_default().to_lowercase(),
+            competitive_tier: p["CompetitiveTier"].as_u64().unwrap_or(0) as u32,
+        }
+    }).collect();
 
     Ok(Some(CurrentMatch {
-        match_id: private["matchMap"].as_str().unwrap_or_default().to_string(),
-        map_id: private["matchMap"].as_str().unwrap_or_default().to_string(),
-        queue_id: private["queueId"].as_str().unwrap_or_default().to_string(),
-        is_ranked: private["competitiveTier"].as_u64().unwrap_or(0) > 0,
-        players: Vec::new(),
+        match_id,
+        players,
+        map_name: resolve_map_name(&map_id),
+        map_id,
+        queue_id,
+        provisioning_flow,
+        phase: phase.to_string(),
+        is_ranked,
     }))
 }
 
-fn resolve_map_name(map_url: &str) -> String {
-    let codename = map_url.split('/').filter(|s| !s.is_empty()).last().unwrap_or("Unknown");
+async fn fetch_names(pd: &Client, shard: &str, puuids: &[String]) -> std::collections::HashMap<String, (String, String)> {
+    let name_url = pd_url(shard, "/name-service/v2/players");
+    match pd.put(&name_url)
+        .json(puuids)
+        .send().await
+    {
+        Ok(resp) => {
+            let arr: Vec<Value> = resp.json().await.unwrap_or_default();
+            arr.iter().filter_map(|v| {
+                let pid = v["Subject"].as_str()?.to_string();
+                let name = v["GameName"].as_str().unwrap_or_default().to_string();
+                let tag = v["TagLine"].as_str().unwrap_or_default().to_string();
+                Some((pid, (name, tag)))
+            }).collect()
+        }
+        Err(_) => std::collections::HashMap::new(),
+    }
+}
+
+pub fn resolve_map_name(map_url: &str) -> String {
+    let codename = map_url.rsplit('/').find(|s| !s.is_empty()).unwrap_or("Unknown");
     match codename.to_lowercase().as_str() {
         "ascent" => "Ascent",
         "duality" => "Bind",
@@ -436,56 +617,102 @@ pub async fn fetch_pregame(tokens: &AuthTokens, puuid: &str, region: &str, shard
 
     let map_id = match_resp["MapID"].as_str().unwrap_or_default().to_string();
     let map_name = resolve_map_name(&map_id);
+    let queue_id = match_resp["QueueID"].as_str().unwrap_or_default().to_string();
+    let phase = match_resp["PregameState"].as_str().unwrap_or_default().to_string();
+    let phase_time_remaining_ms = match_resp["PhaseTimeRemainingNS"].as_u64().unwrap_or(0) / 1_000_000;
+    let team_id = match_resp["AllyTeam"]["TeamID"].as_str().unwrap_or_default().to_string();
+
+    let allies: Vec<PregamePlayer> = match_resp["AllyTeam"]["Players"].as_array()
+        .map(|players| players.iter().map(|p| PregamePlayer {
+            puuid: p["Subject"].as_str().unwrap_or_default().to_string(),
+            character_id: p["CharacterID"].as_str().filter(|s| !s.is_empty()).map(|s| s.to_string()),
+            selection_state: p["CharacterSelectionState"].as_str().unwrap_or_default().to_string(),
+            is_self: p["Subject"].as_str() == Some(puuid),
+            is_captain: p["IsCaptain"].as_bool().unwrap_or(false),
+            competitive_tier: p["CompetitiveTier"].as_u64().unwrap_or(0) as u32,
+            account_level: p["PlayerIdentity"]["AccountLevel"].as_u64().unwrap_or(0) as u32,
+            incognito: p["PlayerIdentity"]["Incognito"].as_bool().unwrap_or(false),
+        }).collect())
+        .unwrap_or_default();
 
-    let mut locked = false;
-    let mut locked_agent: Option<String> = None;
-
-    if let Some(players) = match_resp["AllyTeam"]["Players"].as_array() {
-        for p in players {
-            if p["Subject"].as_str() == Some(puuid) {
-                let char_id = p["CharacterID"].as_str().unwrap_or_default();
-                let selection_state = p["CharacterSelectionState"].as_str().unwrap_or_default();
-                if selection_state == "locked" && !char_id.is_empty() {
-                    locked = true;
-                    locked_agent = Some(char_id.to_string());
-                }
-                break;
-            }
+    let me = allies.iter().find(|p| p.is_self);
+    let locked = me.is_some_and(|p| p.selection_state == "locked" && p.character_id.is_some());
+    let locked_agent = if locked { me.and_then(|p| p.character_id.clone()) } else { None };
+    let selected_agent = me.filter(|p| p.selection_state == "selected").and_then(|p| p.character_id.clone());
+
+    Some(PregameState {
+        match_id,
+        map_id,
+        map_name,
+        queue_id,
+        phase,
+        phase_time_remaining_ms,
+        team_id,
+        locked,
+        locked_agent,
+        selected_agent,
+        allies,
+    })
+}
+
+#[derive(Clone, Copy)]
+pub enum PregameAction<'a> {
+    Select(&'a str),
+    Lock(&'a str),
+    Quit,
+}
+
+impl PregameAction<'_> {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PregameAction::Select(_) => "select",
+            PregameAction::Lock(_) => "lock",
+            PregameAction::Quit => "quit",
         }
     }
-
-    Some(PregameState { match_id, map_id, map_name, locked, locked_agent })
 }
 
-pub async fn lock_agent(tokens: &AuthTokens, region: &str, shard: &str, match_id: &str, agent_id: &str) -> Result<(), String> {
+pub async fn pregame_action(tokens: &AuthTokens, region: &str, shard: &str, match_id: &str, action: PregameAction<'_>) -> Result<u16, String> {
     let client = glz_client(tokens).ok_or("no client")?;
-    let url = glz_url(region, shard, &format!("/pregame/v1/matches/{}/lock/{}", match_id, agent_id));
-    let resp = client.post(&url).send().await.map_err(|e| e.to_string())?;
-    if resp.status().is_success() {
+    let path = match action {
+        PregameAction::Select(agent_id) => format!("/pregame/v1/matches/{}/select/{}", match_id, agent_id),
+        PregameAction::Lock(agent_id) => format!("/pregame/v1/matches/{}/lock/{}", match_id, agent_id),
+        PregameAction::Quit => format!("/pregame/v1/matches/{}/quit", match_id),
+    };
+    let resp = client.post(glz_url(region, shard, &path)).send().await.map_err(|e| e.to_string())?;
+    Ok(resp.status().as_u16())
+}
+
+pub fn action_result(action: PregameAction<'_>, status: u16) -> Result<(), String> {
+    if (200..300).contains(&status) {
         Ok(())
     } else {
-        Err(format!("lock failed: {}", resp.status()))
+        Err(format!("{} failed: {}", action.name(), status))
     }
 }
 
+async fn run_action(tokens: &AuthTokens, region: &str, shard: &str, match_id: &str, action: PregameAction<'_>) -> Result<(), String> {
+    let status = pregame_action(tokens, region, shard, match_id, action).await?;
+    action_result(action, status)
+}
+
+pub async fn lock_agent(tokens: &AuthTokens, region: &str, shard: &str, match_id: &str, agent_id: &str) -> Result<(), String> {
+    run_action(tokens, region, shard, match_id, PregameAction::Lock(agent_id)).await
+}
+
 pub async fn quit_pregame(tokens: &AuthTokens, region: &str, shard: &str, match_id: &str) -> Result<(), String> {
-    let client = glz_client(tokens).ok_or("no client")?;
-    let url = glz_url(region, shard, &format!("/pregame/v1/matches/{}/quit", match_id));
-    let resp = client.post(&url).send().await.map_err(|e| e.to_string())?;
-    if resp.status().is_success() {
-        Ok(())
-    } else {
-        Err(format!("quit failed: {}", resp.status()))
-    }
+    run_action(tokens, region, shard, match_id, PregameAction::Quit).await
 }
 
 use std::sync::Mutex as StdMutex;
 use once_cell::sync::Lazy;
 
-static LIVE_MATCH_CACHE: Lazy<StdMutex<(String, std::collections::HashMap<String, (u32, u32, u32)>)>> =
+type MmrCache = (String, std::collections::HashMap<String, (u32, u32, u32)>);
+
+static LIVE_MATCH_CACHE: Lazy<StdMutex<MmrCache>> =
     Lazy::new(|| StdMutex::new((String::new(), std::collections::HashMap::new())));
 
-pub async fn fetch_live_match(tokens: &AuthTokens, puuid: &str, region: &str, shard: &str) -> Option<LiveMatch> {
+pub async fn fetch_live_match(tokens: &AuthTokens, puuid: &str, region: &str, shard: &str, presence_parties: &std::collections::HashMap<String, String>) -> Option<LiveMatch> {
     let client = glz_client(tokens)?;
     let pd = pd_client(tokens)?;
 
@@ -516,6 +743,8 @@ pub async fn fetch_live_match(tokens: &AuthTokens, puuid: &str, region: &str, sh
         } else { return None; }
     } else { return None; };
 
+    let mut party_ids = presence_parties.clone();
+
     let (map_id, queue_id, raw_players, my_team) = if phase == "pregame" {
         let match_url = glz_url(region, shard, &format!("/pregame/v1/matches/{}", match_id));
         let match_data: Value = client.get(&match_url).send().await.ok()?.json().await.ok()?;
@@ -532,6 +761,9 @@ pub async fn fetch_live_match(tokens: &AuthTokens, puuid: &str, region: &str, sh
                 let incognito = p["PlayerIdentity"]["Incognito"].as_bool().unwrap_or(false);
                 let level = p["PlayerIdentity"]["AccountLevel"].as_u64().unwrap_or(0) as u32;
                 let tier = p["CompetitiveTier"].as_u64().unwrap_or(0) as u32;
+                if let Some(party_id) = p["PartyID"].as_str().filter(|s| !s.is_empty()) {
+                    party_ids.insert(pid.clone(), party_id.to_string());
+                }
                 players.push((pid, char_id, my_team_id.clone(), incognito, level, tier));
             }
         }
@@ -557,6 +789,9 @@ pub async fn fetch_live_match(tokens: &AuthTokens, puuid: &str, region: &str, sh
                 if pid == puuid {
                     my_team_id = team_id.clone();
                 }
+                if let Some(party_id) = p["PartyID"].as_str().filter(|s| !s.is_empty()) {
+                    party_ids.insert(pid.clone(), party_id.to_string());
+                }
                 players.push((pid, char_id, team_id, incognito, level, tier));
             }
         }
@@ -568,22 +803,7 @@ pub async fn fetch_live_match(tokens: &AuthTokens, puuid: &str, region: &str, sh
 
     let puuids: Vec<String> = raw_players.iter().map(|(pid, _, _, _, _, _)| pid.clone()).collect();
 
-    let name_url = pd_url(shard, "/name-service/v2/players");
-    let names: std::collections::HashMap<String, (String, String)> = match pd.put(&name_url)
-        .json(&puuids)
-        .send().await
-    {
-        Ok(resp) => {
-            let arr: Vec<Value> = resp.json().await.unwrap_or_default();
-            arr.iter().filter_map(|v| {
-                let pid = v["Subject"].as_str()?.to_string();
-                let name = v["GameName"].as_str().unwrap_or_default().to_string();
-                let tag = v["TagLine"].as_str().unwrap_or_default().to_string();
-                Some((pid, (name, tag)))
-            }).collect()
-        }
-        Err(_) => std::collections::HashMap::new(),
-    };
+    let names = fetch_names(&pd, shard, &puuids).await;
 
     let cached_mmr = {
         let cache = LIVE_MATCH_CACHE.lock().unwrap();
@@ -603,7 +823,7 @@ pub async fn fetch_live_match(tokens: &AuthTokens, puuid: &str, region: &str, sh
         }
         for pid in &puuids {
             if pid == puuid { continue; }
-            tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+            PD_LIMITER.acquire().await;
             if let Some(mmr) = fetch_mmr(tokens, pid, shard).await {
                 map.insert(pid.clone(), (mmr.rank, mmr.rr, mmr.peak_rank));
             }
@@ -615,10 +835,26 @@ pub async fn fetch_live_match(tokens: &AuthTokens, puuid: &str, region: &str, sh
 
     let agents = fetch_agent_map().await;
 
+    let mut party_sizes: std::collections::HashMap<&str, u32> = std::collections::HashMap::new();
+    for pid in &puuids {
+        if let Some(party_id) = party_ids.get(pid) {
+            *party_sizes.entry(party_id.as_str()).or_default() += 1;
+        }
+    }
+    let mut party_groups: std::collections::HashMap<&str, u32> = std::collections::HashMap::new();
+    for pid in &puuids {
+        if let Some(party_id) = party_ids.get(pid) {
+            if party_sizes[party_id.as_str()] > 1 && !party_groups.contains_key(party_id.as_str()) {
+                let next = party_groups.len() as u32 + 1;
+                party_groups.insert(party_id.as_str(), next);
+            }
+        }
+    }
+
     let mut ally_team: Vec<LiveMatchPlayer> = Vec::new();
     let mut enemy_team: Vec<LiveMatchPlayer> = Vec::new();
 
-    for (_i, (pid, char_id, team_id, incognito, level, _tier)) in raw_players.iter().enumerate() {
+    for (pid, char_id, team_id, incognito, level, _tier) in raw_players.iter() {
         let (game_name, tag_line) = names.get(pid).cloned().unwrap_or_default();
 
         let (agent_name, agent_icon) = agents.get(char_id.as_str())
@@ -641,6 +877,10 @@ pub async fn fetch_live_match(tokens: &AuthTokens, puuid: &str, region: &str, sh
             account_level: *level,
             incognito: *incognito,
             is_self: pid == puuid,
+            party_group: party_ids.get(pid).and_then(|id| party_groups.get(id.as_str()).copied()),
+            encounters: None,
+            watch_tags: Vec::new(),
+            recent_form: None,
         };
 
         if team_id == &my_team {
@@ -654,11 +894,11 @@ pub async fn fetch_live_match(tokens: &AuthTokens, puuid: &str, region: &str, sh
     let is_team_mode = !ffa_queues.contains(&queue_id.as_str());
 
     if !is_team_mode {
-        ally_team.extend(enemy_team.drain(..));
+        ally_team.append(&mut enemy_team);
     }
 
-    ally_team.sort_by(|a, b| b.rank.cmp(&a.rank));
-    enemy_team.sort_by(|a, b| b.rank.cmp(&a.rank));
+    ally_team.sort_by_key(|p| std::cmp::Reverse(p.rank));
+    enemy_team.sort_by_key(|p| std::cmp::Reverse(p.rank));
 
     Some(LiveMatch {
         match_id,
@@ -669,10 +909,139 @@ pub async fn fetch_live_match(tokens: &AuthTokens, puuid: &str, region: &str, sh
         is_team_mode,
         ally_team,
         enemy_team,
+        team_summary: None,
+    })
+}
+
+const SKIN_SOCKET: &str = "bcef87d6-209b-46c6-8b19-fbe40bd95abc";
+const CHROMA_SOCKET: &str = "3ad1b2b2-acdb-4524-852f-954a76ddae0a";
+const BUDDY_SOCKET: &str = "77258665-71d1-4623-bc72-44db9bd5b3b3";
+
+fn parse_loadout(loadout: &Value, puuid: &str, team_id: &str, agent_id: &str, catalog: &ContentCatalog) -> PlayerLoadout {
+    let mut weapons: Vec<WeaponLoadout> = loadout["Items"].as_object()
+        .map(|items| items.iter().map(|(weapon_id, item)| {
+            let socket = |id: &str| item["Sockets"][id]["Item"]["ID"].as_str().unwrap_or_default().to_string();
+            WeaponLoadout {
+                weapon_id: weapon_id.to_lowercase(),
+                weapon_name: catalog.weapon_name(weapon_id),
+                skin: catalog.skin(&socket(SKIN_SOCKET), &socket(CHROMA_SOCKET)),
+                buddy: catalog.buddy(&socket(BUDDY_SOCKET)),
+            }
+        }).collect())
+        .unwrap_or_default();
+    weapons.sort_by(|a, b| a.weapon_name.cmp(&b.weapon_name));
+
+    let sprays = loadout["Sprays"]["SpraySelections"].as_array()
+        .map(|arr| arr.iter()
+            .filter_map(|s| s["SprayID"].as_str())
+            .filter_map(|id| catalog.spray(id))
+            .collect())
+        .unwrap_or_default();
+
+    PlayerLoadout {
+        puuid: puuid.to_string(),
+        team_id: team_id.to_string(),
+        agent_id: agent_id.to_lowercase(),
+        weapons,
+        sprays,
+    }
+}
+
+pub async fn fetch_penalties(tokens: &AuthTokens, shard: &str) -> Option<PenaltyRestrictions> {
+    let client = pd_client(tokens)?;
+    let url = pd_url(shard, "/restrictions/v3/penalties");
+    let resp = client.get(&url).send().await.ok()?;
+    if !resp.status().is_success() {
+        println!("[penalties] failed — status {}", resp.status());
+        return None;
+    }
+    let data: Value = resp.json().await.ok()?;
+
+    let now = chrono::Utc::now();
+    let active: Vec<(&Value, u64)> = data["Penalties"].as_array()
+        .map(|list| list.iter()
+            .filter_map(|p| {
+                let expiry = chrono::DateTime::parse_from_rfc3339(p["Expiry"].as_str()?).ok()?;
+                (expiry > now).then(|| (p, expiry.timestamp_millis() as u64))
+            })
+            .collect())
+        .unwrap_or_default();
+
+    Some(PenaltyRestrictions {
+        active_penalties: active.len() as u32,
+        infractions: data["Infractions"].as_array().map(|a| a.len() as u32).unwrap_or(0),
+        queue_restricted: active.iter().any(|(p, _)| !p["QueueRestrictionEffect"].is_null() || !p["QueueDelayEffect"].is_null()),
+        expires_at: active.iter().map(|(_, expiry)| *expiry).max(),
     })
 }
 
-async fn fetch_agent_map() -> std::collections::HashMap<String, (String, String)> {
+pub async fn fetch_match_loadouts(tokens: &AuthTokens, puuid: &str, region: &str, shard: &str) -> Option<MatchLoadouts> {
+    let client = glz_client(tokens)?;
+
+    let pregame_url = glz_url(region, shard, &format!("/pregame/v1/players/{}", puuid));
+    let coregame_url = glz_url(region, shard, &format!("/core-game/v1/players/{}", puuid));
+
+    let match_id_from = |resp: Option<Value>| resp
+        .and_then(|v| v["MatchID"].as_str().map(|s| s.to_string()))
+        .filter(|s| !s.is_empty());
+
+    let pregame_id = match client.get(&pregame_url).send().await {
+        Ok(r) if r.status().is_success() => match_id_from(r.json().await.ok()),
+        _ => None,
+    };
+
+    let (match_id, phase) = match pregame_id {
+        Some(id) => (id, "pregame"),
+        None => {
+            let coregame_id = match client.get(&coregame_url).send().await {
+                Ok(r) if r.status().is_success() => match_id_from(r.json().await.ok()),
+                _ => None,
+            };
+            (coregame_id?, "ingame")
+        }
+    };
+
+    let base = if phase == "pregame" { "pregame" } else { "core-game" };
+    let match_url = glz_url(region, shard, &format!("/{}/v1/matches/{}", base, match_id));
+    let loadouts_url = glz_url(region, shard, &format!("/{}/v1/matches/{}/loadouts", base, match_id));
+
+    let match_data: Value = client.get(&match_url).send().await.ok()?.json().await.ok()?;
+    let loadouts: Value = client.get(&loadouts_url).send().await.ok()?.json().await.ok()?;
+    let catalog = content::catalog().await;
+
+    let players = if phase == "pregame" {
+        let team_id = match_data["AllyTeam"]["TeamID"].as_str().unwrap_or_default();
+        let roster = match_data["AllyTeam"]["Players"].as_array().cloned().unwrap_or_default();
+        loadouts["Loadouts"].as_array()
+            .map(|arr| arr.iter().enumerate().filter_map(|(i, loadout)| {
+                let player = loadout["Subject"].as_str()
+                    .and_then(|s| roster.iter().find(|p| p["Subject"].as_str() == Some(s)))
+                    .or_else(|| roster.get(i))?;
+                let pid = player["Subject"].as_str().unwrap_or_default();
+                let agent_id = player["CharacterID"].as_str().unwrap_or_default();
+                Some(parse_loadout(loadout, pid, team_id, agent_id, &catalog))
+            }).collect())
+            .unwrap_or_default()
+    } else {
+        let roster = match_data["Players"].as_array().cloned().unwrap_or_default();
+        loadouts["Loadouts"].as_array()
+            .map(|arr| arr.iter().map(|entry| {
+                let loadout = &entry["Loadout"];
+                let pid = loadout["Subject"].as_str().unwrap_or_default();
+                let team_id = roster.iter()
+                    .find(|p| p["Subject"].as_str() == Some(pid))
+                    .and_then(|p| p["TeamID"].as_str())
+                    .unwrap_or_default();
+                let agent_id = entry["CharacterID"].as_str().unwrap_or_default();
+                parse_loadout(loadout, pid, team_id, agent_id, &catalog)
+            }).collect())
+            .unwrap_or_default()
+    };
+
+    Some(MatchLoadouts { match_id, phase: phase.to_string(), players })
+}
+
+pub async fn fetch_agent_map() -> std::collections::HashMap<String, (String, String)> {
     let mut map = std::collections::HashMap::new();
     let resp: Value = match reqwest::get("https://valorant-api.com/v1/agents?isPlayableCharacter=true").await {
         Ok(r) => match r.json().await { Ok(v) => v, Err(_) => return map },
@@ -689,15 +1058,144 @@ async fn fetch_agent_map() -> std::collections::HashMap<String, (String, String)
     map
 }
 
-pub async fn select_agent(tokens: &AuthTokens, region: &str, shard: &str, match_id: &str, agent_id: &str) -> Result<(), String> {
-    let client = glz_client(tokens).ok_or("no client")?;
-    let url = glz_url(region, shard, &format!("/pregame/v1/matches/{}/select/{}", match_id, agent_id));
-    let resp = client.post(&url).send().await.map_err(|e| e.to_string())?;
-    if resp.status().is_success() {
-        Ok(())
-    } else {
-        Err(format!("select failed: {}", resp.status()))
+fn decode_private_presence(presence: &Value) -> Option<Value> {
+    let b64 = presence["private"].as_str().filter(|s| !s.is_empty())?;
+    let bytes = BASE64.decode(b64).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+pub async fn fetch_presence_parties(client: &Client, lock: &Lockfile) -> std::collections::HashMap<String, String> {
+    let url = format!("{}/chat/v4/presences", base_url(lock));
+    let resp: Value = match client.get(&url).send().await {
+        Ok(r) => r.json().await.unwrap_or_default(),
+        Err(_) => return std::collections::HashMap::new(),
+    };
+
+    resp["presences"].as_array()
+        .map(|arr| arr.iter().filter_map(|p| {
+            let pid = p["puuid"].as_str()?.to_string();
+            let private = decode_private_presence(p)?;
+            let party_id = private["partyId"].as_str()
+                .or_else(|| private["partyPresenceData"]["partyId"].as_str())
+                .filter(|s| !s.is_empty())?
+                .to_string();
+            Some((pid, party_id))
+        }).collect())
+        .unwrap_or_default()
+}
+
+pub async fn fetch_self_presence(client: &Client, lock: &Lockfile, puuid: &str) -> Option<Value> {
+    let url = format!("{}/chat/v4/presences", base_url(lock));
+    let resp: Value = client.get(&url).send().await.ok()?.json().await.ok()?;
+    resp["presences"].as_array()?
+        .iter()
+        .filter(|p| p["puuid"].as_str() == Some(puuid))
+        .find_map(decode_private_presence)
+}
+
+pub async fn fetch_match_meta(tokens: &AuthTokens, puuid: &str, region: &str, shard: &str, pregame: bool) -> Option<(String, String, String)> {
+    let client = glz_client(tokens)?;
+    let base = if pregame { "pregame" } else { "core-game" };
+
+    let player_url = glz_url(region, shard, &format!("/{}/v1/players/{}", base, puuid));
+    let resp = client.get(&player_url).send().await.ok()?;
+    if !resp.status().is_success() {
+        return None;
+    }
+    let player: Value = resp.json().await.ok()?;
+    let match_id = player["MatchID"].as_str().filter(|s| !s.is_empty())?.to_string();
+
+    let match_url = glz_url(region, shard, &format!("/{}/v1/matches/{}", base, match_id));
+    let match_data: Value = match client.get(&match_url).send().await {
+        Ok(r) if r.status().is_success() => r.json().await.unwrap_or_default(),
+        _ => Value::Null,
+    };
+    let map_id = match_data["MapID"].as_str().unwrap_or_default().to_string();
+    let queue_id = match_data["QueueID"].as_str()
+        .or_else(|| match_data["MatchmakingData"]["QueueID"].as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    Some((match_id, map_id, queue_id))
+}
+
+fn decode_live_presence(presence: &Value, self_puuid: &str) -> Option<LivePresence> {
+    if presence["product"].as_str().is_some_and(|p| p != "valorant") {
+        return None;
+    }
+    let private = decode_private_presence(presence)?;
+    let puuid = presence["puuid"].as_str()?.to_string();
+
+    let text = |key: &str, nested: &str| private[key].as_str()
+        .filter(|s| !s.is_empty())
+        .or_else(|| private[nested][key].as_str())
+        .unwrap_or_default()
+        .to_string();
+    let number = |key: &str, nested: &str| private[key].as_u64()
+        .or_else(|| private[nested][key].as_u64())
+        .unwrap_or(0) as u32;
+    let flag = |key: &str, nested: &str| private[key].as_bool()
+        .or_else(|| private[nested][key].as_bool())
+        .unwrap_or(false);
+
+    let map_id = text("matchMap", "matchPresenceData");
+    let party_size = number("partySize", "partyPresenceData");
+    let queue_id = match text("queueId", "matchPresenceData") {
+        q if q.is_empty() => text("queueId", "partyPresenceData"),
+        q => q,
+    };
+
+    Some(LivePresence {
+        is_self: puuid == self_puuid,
+        puuid,
+        game_name: presence["game_name"].as_str().unwrap_or_default().to_string(),
+        tag_line: presence["game_tag"].as_str().unwrap_or_default().to_string(),
+        session_loop_state: text("sessionLoopState", "matchPresenceData"),
+        map_name: if map_id.is_empty() { String::new() } else { resolve_map_name(&map_id) },
+        map_id,
+        queue_id,
+        ally_score: number("partyOwnerMatchScoreAllyTeam", "partyPresenceData"),
+        enemy_score: number("partyOwnerMatchScoreEnemyTeam", "partyPresenceData"),
+        party_id: text("partyId", "partyPresenceData"),
+        party_state: text("partyState", "partyPresenceData"),
+        party_size,
+        max_party_size: number("maxPartySize", "partyPresenceData"),
+        is_party_owner: flag("isPartyOwner", "partyPresenceData"),
+        in_party: party_size > 1,
+    })
+}
+
+pub async fn fetch_live_presences(client: &Client, lock: &Lockfile, self_puuid: &str) -> Vec<LivePresence> {
+    let url = format!("{}/chat/v4/presences", base_url(lock));
+    let resp: Value = match client.get(&url).send().await {
+        Ok(r) => r.json().await.unwrap_or_default(),
+        Err(_) => return Vec::new(),
+    };
+
+    resp["presences"].as_array()
+        .map(|arr| arr.iter().filter_map(|p| decode_live_presence(p, self_puuid)).collect())
+        .unwrap_or_default()
+}
+
+pub async fn fetch_map_info() -> std::collections::HashMap<String, (String, String)> {
+    let mut map = std::collections::HashMap::new();
+    let resp: Value = match reqwest::get("https://valorant-api.com/v1/maps").await {
+        Ok(r) => match r.json().await { Ok(v) => v, Err(_) => return map },
+        Err(_) => return map,
+    };
+    if let Some(maps) = resp["data"].as_array() {
+        for m in maps {
+            let url = m["mapUrl"].as_str().unwrap_or_default().to_lowercase();
+            let name = m["displayName"].as_str().unwrap_or_default().to_string();
+            let splash = m["splash"].as_str().unwrap_or_default().to_string();
+            map.insert(url, (name, splash));
+        }
     }
+    map
+}
+
+pub async fn select_agent(tokens: &AuthTokens, region: &str, shard: &str, match_id: &str, agent_id: &str) -> Result<(), String> {
+    run_action(tokens, region, shard, match_id, PregameAction::Select(agent_id)).await
 }
 
 pub async fn fetch_party(tokens: &AuthTokens, puuid: &str, region: &str, shard: &str) -> Option<PartyState> {
@@ -716,22 +1214,7 @@ pub async fn fetch_party(tokens: &AuthTokens, puuid: &str, region: &str, shard:
         .filter_map(|m| m["Subject"].as_str().map(|s| s.to_string()))
         .collect();
 
-    let name_url = pd_url(shard, "/name-service/v2/players");
-    let names: std::collections::HashMap<String, (String, String)> = match pd.put(&name_url)
-        .json(&puuids)
-        .send().await
-    {
-        Ok(resp) => {
-            let arr: Vec<Value> = resp.json().await.unwrap_or_default();
-            arr.iter().filter_map(|v| {
-                let pid = v["Subject"].as_str()?.to_string();
-                let name = v["GameName"].as_str().unwrap_or_default().to_string();
-                let tag = v["TagLine"].as_str().unwrap_or_default().to_string();
-                Some((pid, (name, tag)))
-            }).collect()
-        }
-        Err(_) => std::collections::HashMap::new(),
-    };
+    let names = fetch_names(&pd, shard, &puuids).await;
 
     let mut is_owner = false;
     let mut members: Vec<PartyMember> = Vec::new();
@@ -778,18 +1261,7 @@ pub async fn fetch_party(tokens: &AuthTokens, puuid: &str, region: &str, shard:
             .filter_map(|r| r["RequestedBySubject"].as_str().map(|s| s.to_string()))
             .collect();
         let req_names: std::collections::HashMap<String, (String, String)> = if !req_puuids.is_empty() {
-            match pd.put(&name_url).json(&req_puuids).send().await {
-                Ok(resp) => {
-                    let arr: Vec<Value> = resp.json().await.unwrap_or_default();
-                    arr.iter().filter_map(|v| {
-                        let pid = v["Subject"].as_str()?.to_string();
-                        let n = v["GameName"].as_str().unwrap_or_default().to_string();
-                        let t = v["TagLine"].as_str().unwrap_or_default().to_string();
-                        Some((pid, (n, t)))
-                    }).collect()
-                }
-                Err(_) => std::collections::HashMap::new(),
-            }
+            fetch_names(&pd, shard, &req_puuids).await
         } else { std::collections::HashMap::new() };
 
         for r in requests {
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use tokio::sync::Mutex;
+use super::types::LoadoutItem;
+
+static CATALOG: Lazy<Mutex<Option<Arc<ContentCatalog>>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Default)]
+pub struct ContentCatalog {
+    weapons: HashMap<String, String>,
+    skins: HashMap<String, LoadoutItem>,
+    chromas: HashMap<String, LoadoutItem>,
+    buddies: HashMap<String, LoadoutItem>,
+    sprays: HashMap<String, LoadoutItem>,
+}
+
+impl ContentCatalog {
+    pub fn weapon_name(&self, id: &str) -> String {
+        self.weapons.get(&id.to_lowercase()).cloned().unwrap_or_default()
+    }
+
+    pub fn skin(&self, skin_id: &str, chroma_id: &str) -> Option<LoadoutItem> {
+        let mut skin = self.skins.get(&skin_id.to_lowercase()).cloned()?;
+        if let Some(chroma) = self.chromas.get(&chroma_id.to_lowercase()) {
+            if !chroma.icon.is_empty() {
+                skin.icon = chroma.icon.clone();
+            }
+        }
+        Some(skin)
+    }
+
+    pub fn buddy(&self, id: &str) -> Option<LoadoutItem> {
+        self.buddies.get(&id.to_lowercase()).cloned()
+    }
+
+    pub fn spray(&self, id: &str) -> Option<LoadoutItem> {
+        self.sprays.get(&id.to_lowercase()).cloned()
+    }
+}
+
+fn item(id: &str, v: &Value, icon_keys: &[&str]) -> LoadoutItem {
+    LoadoutItem {
+        id: id.to_string(),
+        name: v["displayName"].as_str().unwrap_or_default().to_string(),
+        icon: icon_keys.iter()
+            .find_map(|k| v[*k].as_str().filter(|s| !s.is_empty()))
+            .unwrap_or_default()
+            .to_string(),
+    }
+}
+
+async fn fetch_data(path: &str) -> Option<Vec<Value>> {
+    let resp: Value = reqwest::get(format!("https://valorant-api.com/v1/{}", path)).await.ok()?.json().await.ok()?;
+    resp["data"].as_array().cloned()
+}
+
+async fn load() -> Option<ContentCatalog> {
+    let mut catalog = ContentCatalog::default();
+
+    for weapon in fetch_data("weapons").await? {
+        let weapon_id = weapon["uuid"].as_str().unwrap_or_default().to_lowercase();
+        catalog.weapons.insert(weapon_id, weapon["displayName"].as_str().unwrap_or_default().to_string());
+
+        for skin in weapon["skins"].as_array().into_iter().flatten() {
+            let skin_id = skin["uuid"].as_str().unwrap_or_default().to_lowercase();
+            let chroma_icon = skin["chromas"][0]["fullRender"].as_str().unwrap_or_default();
+            let mut entry = item(&skin_id, skin, &["displayIcon"]);
+            if entry.icon.is_empty() {
+                entry.icon = chroma_icon.to_string();
+            }
+            catalog.skins.insert(skin_id, entry);
+
+            for chroma in skin["chromas"].as_array().into_iter().flatten() {
+                let chroma_id = chroma["uuid"].as_str().unwrap_or_default().to_lowercase();
+                catalog.chromas.insert(chroma_id.clone(), item(&chroma_id, chroma, &["fullRender", "displayIcon"]));
+            }
+        }
+    }
+
+    for buddy in fetch_data("buddies").await.unwrap_or_default() {
+        let buddy_id = buddy["uuid"].as_str().unwrap_or_default().to_lowercase();
+        let entry = item(&buddy_id, &buddy, &["displayIcon"]);
+        for level in buddy["levels"].as_array().into_iter().flatten() {
+            let level_id = level["uuid"].as_str().unwrap_or_default().to_lowercase();
+            catalog.buddies.insert(level_id, entry.clone());
+        }
+        catalog.buddies.insert(buddy_id, entry);
+    }
+
+    for spray in fetch_data("sprays").await.unwrap_or_default() {
+        let spray_id = spray["uuid"].as_str().unwrap_or_default().to_lowercase();
+        let entry = item(&spray_id, &spray, &["fullTransparentIcon", "displayIcon"]);
+        for level in spray["levels"].as_array().into_iter().flatten() {
+            let level_id = level["uuid"].as_str().unwrap_or_default().to_lowercase();
+            catalog.sprays.insert(level_id, entry.clone());
+        }
+        catalog.sprays.insert(spray_id, entry);
+    }
+
+    Some(catalog)
+}
+
+pub async fn catalog() -> Arc<ContentCatalog> {
+    let mut cached = CATALOG.lock().await;
+    if let Some(catalog) = cached.as_ref() {
+        return catalog.clone();
+    }
+    match load().await {
+        Some(catalog) => {
+            let catalog = Arc::new(catalog);
+            *cached = Some(catalog.clone());
+            catalog
+        }
+        None => Arc::new(ContentCatalog::default()),
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use once_cell::sync::Lazy;
+use super::api;
+use super::types::{AuthTokens, LiveMatch, RecentForm};
+
+const FORM_MATCHES: usize = 5;
+const FORM_TTL: Duration = Duration::from_secs(15 * 60);
+
+type FormCache = HashMap<String, (Instant, Option<RecentForm>)>;
+
+static CACHE: Lazy<Mutex<FormCache>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static PENDING: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+struct PendingGuard(Vec<String>);
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        let mut pending = PENDING.lock().unwrap();
+        for puuid in &self.0 {
+            pending.remove(puuid);
+        }
+    }
+}
+
+fn cached(puuid: &str) -> Option<Option<RecentForm>> {
+    let cache = CACHE.lock().unwrap();
+    cache.get(puuid)
+        .filter(|(at, _)| at.elapsed() < FORM_TTL)
+        .map(|(_, form)| form.clone())
+}
+
+pub fn annotate(live: &mut LiveMatch) {
+    for player in live.ally_team.iter_mut().chain(live.enemy_team.iter_mut()) {
+        player.recent_form = cached(&player.puuid).flatten();
+    }
+}
+
+pub fn prefetch(tokens: &AuthTokens, shard: &str, live: &LiveMatch) {
+    let missing: Vec<String> = {
+        let mut pending = PENDING.lock().unwrap();
+        live.ally_team.iter().chain(live.enemy_team.iter())
+            .map(|p| p.puuid.clone())
+            .filter(|puuid| cached(puuid).is_none() && pending.insert(puuid.clone()))
+            .collect()
+    };
+    if missing.is_empty() {
+        return;
+    }
+
+    let tokens = tokens.clone();
+    let shard = shard.to_string();
+    let queue_id = live.queue_id.clone();
+    let guard = PendingGuard(missing);
+    tauri::async_runtime::spawn(async move {
+        let agents = api::fetch_agent_map().await;
+        for puuid in &guard.0 {
+            let form = api::fetch_recent_form(&tokens, puuid, &shard, &queue_id, FORM_MATCHES, &agents).await;
+            CACHE.lock().unwrap().insert(puuid.clone(), (Instant::now(), form));
+        }
+    });
+}
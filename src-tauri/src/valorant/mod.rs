@@ -1,4 +1,9 @@
 pub mod api;
 pub mod connection;
+pub mod content;
+pub mod form;
 pub mod lockfile;
+pub mod phase;
+pub mod ratelimit;
+pub mod score;
 pub mod types;
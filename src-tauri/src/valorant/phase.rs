@@ -0,0 +1,189 @@
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::Mutex;
+use crate::store::now_ms;
+use super::api;
+use super::connection::ValorantConnection;
+use super::types::ConnectionStatus;
+
+const POSTGAME_HOLD: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MatchPhase {
+    #[default]
+    Menus,
+    Queueing,
+    MatchFound,
+    Pregame,
+    InGame,
+    PostGame,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseSnapshot {
+    pub phase: MatchPhase,
+    pub match_id: String,
+    pub map_id: String,
+    pub map_name: String,
+    pub queue_id: String,
+    pub since: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseTransition {
+    pub from: MatchPhase,
+    pub to: MatchPhase,
+    pub match_id: String,
+    pub map_id: String,
+    pub map_name: String,
+    pub queue_id: String,
+    pub finished_match_id: String,
+    pub timestamp: u64,
+}
+
+struct TrackerState {
+    snapshot: PhaseSnapshot,
+    entered: Instant,
+}
+
+pub struct MatchPhaseTracker {
+    state: Mutex<TrackerState>,
+}
+
+fn presence_str<'a>(presence: &'a Value, key: &str, nested: &str) -> &'a str {
+    presence[key].as_str()
+        .filter(|s| !s.is_empty())
+        .or_else(|| presence[nested][key].as_str())
+        .unwrap_or_default()
+}
+
+impl MatchPhaseTracker {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(TrackerState { snapshot: PhaseSnapshot::default(), entered: Instant::now() }),
+        }
+    }
+
+    pub async fn current(&self) -> PhaseSnapshot {
+        self.state.lock().await.snapshot.clone()
+    }
+
+    async fn observe(&self, conn: &ValorantConnection, previous: &TrackerState) -> PhaseSnapshot {
+        let mut next = PhaseSnapshot::default();
+
+        let state = conn.get_state().await;
+        if state.status != ConnectionStatus::Connected {
+            return next;
+        }
+        let (Some(puuid), Some(region), Some(shard), Some(tokens)) = (
+            state.player_info.as_ref().map(|i| i.puuid.clone()),
+            state.region,
+            state.shard,
+            conn.get_tokens().await,
+        ) else {
+            return next;
+        };
+
+        let presence = match conn.get_client_and_lock().await {
+            Some((client, lock)) => api::fetch_self_presence(&client, &lock, &puuid).await,
+            None => None,
+        };
+        let (loop_state, party_state) = match presence.as_ref() {
+            Some(p) => (
+                presence_str(p, "sessionLoopState", "matchPresenceData").to_uppercase(),
+                presence_str(p, "partyState", "partyPresenceData").to_uppercase(),
+            ),
+            None => (String::new(), String::new()),
+        };
+        if let Some(p) = presence.as_ref() {
+            next.map_id = presence_str(p, "matchMap", "matchPresenceData").to_string();
+            next.queue_id = presence_str(p, "queueId", "matchPresenceData").to_string();
+        }
+
+        let check_pregame = loop_state.is_empty() || loop_state == "PREGAME";
+        let check_ingame = loop_state.is_empty() || loop_state == "INGAME";
+
+        let mut meta = None;
+        if check_pregame {
+            meta = api::fetch_match_meta(&tokens, &puuid, &region, &shard, true).await.map(|m| (MatchPhase::Pregame, m));
+        }
+        if meta.is_none() && check_ingame {
+            meta = api::fetch_match_meta(&tokens, &puuid, &region, &shard, false).await.map(|m| (MatchPhase::InGame, m));
+        }
+
+        if let Some((phase, (match_id, map_id, queue_id))) = meta {
+            next.phase = phase;
+            next.match_id = match_id;
+            if !map_id.is_empty() { next.map_id = map_id; }
+            if !queue_id.is_empty() { next.queue_id = queue_id; }
+        } else if loop_state == "PREGAME" {
+            next.phase = MatchPhase::Pregame;
+        } else if loop_state == "INGAME" {
+            next.phase = MatchPhase::InGame;
+        } else if party_state == "MATCHMAKING" {
+            next.phase = MatchPhase::Queueing;
+        } else if party_state == "MATCHMADE_GAME_STARTING" {
+            next.phase = MatchPhase::MatchFound;
+        } else if previous.snapshot.phase == MatchPhase::InGame
+            || (previous.snapshot.phase == MatchPhase::PostGame && previous.entered.elapsed() < POSTGAME_HOLD) {
+            next.phase = MatchPhase::PostGame;
+            next.match_id = previous.snapshot.match_id.clone();
+            next.map_id = previous.snapshot.map_id.clone();
+            next.queue_id = previous.snapshot.queue_id.clone();
+        }
+
+        if next.phase == MatchPhase::Menus {
+            next.map_id.clear();
+        }
+        if next.match_id.is_empty() && next.phase == previous.snapshot.phase {
+            next.match_id = previous.snapshot.match_id.clone();
+        }
+        next
+    }
+
+    pub async fn poll(&self, conn: &ValorantConnection) -> Option<PhaseTransition> {
+        let previous = {
+            let state = self.state.lock().await;
+            TrackerState { snapshot: state.snapshot.clone(), entered: state.entered }
+        };
+        let mut next = self.observe(conn, &previous).await;
+
+        if next.phase == previous.snapshot.phase && next.match_id == previous.snapshot.match_id {
+            let mut state = self.state.lock().await;
+            next.since = state.snapshot.since;
+            next.map_name = if next.map_id == state.snapshot.map_id {
+                state.snapshot.map_name.clone()
+            } else {
+                api::resolve_map_name(&next.map_id)
+            };
+            state.snapshot = next;
+            return None;
+        }
+
+        next.since = now_ms();
+        next.map_name = if next.map_id.is_empty() { String::new() } else { api::resolve_map_name(&next.map_id) };
+
+        let transition = PhaseTransition {
+            from: previous.snapshot.phase,
+            to: next.phase,
+            match_id: next.match_id.clone(),
+            map_id: next.map_id.clone(),
+            map_name: next.map_name.clone(),
+            queue_id: next.queue_id.clone(),
+            finished_match_id: if previous.snapshot.phase == MatchPhase::InGame && next.phase != MatchPhase::InGame {
+                previous.snapshot.match_id.clone()
+            } else {
+                String::new()
+            },
+            timestamp: next.since,
+        };
+
+        let mut state = self.state.lock().await;
+        state.snapshot = next;
+        state.entered = Instant::now();
+        Some(transition)
+    }
+}
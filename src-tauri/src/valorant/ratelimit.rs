@@ -0,0 +1,29 @@
+use std::time::{Duration, Instant};
+use once_cell::sync::Lazy;
+use tokio::sync::Mutex;
+
+pub struct RateLimiter {
+    interval: Duration,
+    next: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(interval: Duration) -> Self {
+        Self { interval, next: Mutex::new(Instant::now()) }
+    }
+
+    pub async fn acquire(&self) {
+        let wait = {
+            let mut next = self.next.lock().await;
+            let now = Instant::now();
+            let slot = (*next).max(now);
+            *next = slot + self.interval;
+            slot - now
+        };
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+pub static PD_LIMITER: Lazy<RateLimiter> = Lazy::new(|| RateLimiter::new(Duration::from_millis(300)));
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use serde::Serialize;
+use tokio::sync::Mutex;
+use super::api;
+use super::connection::ValorantConnection;
+use super::types::{ConnectionStatus, LivePresence};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresenceChange {
+    pub presence: LivePresence,
+    pub score_changed: bool,
+}
+
+pub struct LiveScoreTracker {
+    presences: Mutex<HashMap<String, LivePresence>>,
+}
+
+pub fn queue_label(queue_id: &str) -> String {
+    match queue_id {
+        "competitive" => "Competitive",
+        "unrated" => "Unrated",
+        "spikerush" => "Spike Rush",
+        "deathmatch" => "Deathmatch",
+        "ggteam" => "Escalation",
+        "newmap" => "New Map",
+        "swiftplay" => "Swiftplay",
+        "premier" => "Premier",
+        "hurm" => "Team Deathmatch",
+        "" => "Custom",
+        other => other,
+    }.to_string()
+}
+
+pub fn describe(presence: &LivePresence) -> Option<String> {
+    if !presence.session_loop_state.eq_ignore_ascii_case("INGAME") || presence.map_name.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{} · {} · {}–{}",
+        presence.map_name,
+        queue_label(&presence.queue_id),
+        presence.ally_score,
+        presence.enemy_score,
+    ))
+}
+
+impl LiveScoreTracker {
+    pub fn new() -> Self {
+        Self { presences: Mutex::new(HashMap::new()) }
+    }
+
+    pub async fn all(&self) -> Vec<LivePresence> {
+        let mut list: Vec<LivePresence> = self.presences.lock().await.values().cloned().collect();
+        list.sort_by(|a, b| b.is_self.cmp(&a.is_self).then(a.game_name.to_lowercase().cmp(&b.game_name.to_lowercase())));
+        list
+    }
+
+    pub async fn own(&self) -> Option<LivePresence> {
+        self.presences.lock().await.values().find(|p| p.is_self).cloned()
+    }
+
+    pub async fn poll(&self, conn: &ValorantConnection) -> Vec<PresenceChange> {
+        let state = conn.get_state().await;
+        let latest = match (state.status, state.player_info, conn.get_client_and_lock().await) {
+            (ConnectionStatus::Connected, Some(info), Some((client, lock))) => api::fetch_live_presences(&client, &lock, &info.puuid).await,
+            _ => Vec::new(),
+        };
+
+        let mut presences = self.presences.lock().await;
+        let mut changes = Vec::new();
+        for presence in &latest {
+            let previous = presences.get(&presence.puuid);
+            if previous == Some(presence) {
+                continue;
+            }
+            let score_changed = previous.is_none_or(|p| p.ally_score != presence.ally_score || p.enemy_score != presence.enemy_score);
+            changes.push(PresenceChange { presence: presence.clone(), score_changed });
+        }
+
+        *presences = latest.into_iter().map(|p| (p.puuid.clone(), p)).collect();
+        changes
+    }
+}
@@ -67,22 +67,38 @@ pub struct PlayerMMR {
     pub games: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CompUpdate {
     pub match_id: String,
+    #[serde(default)]
     pub map_id: String,
+    #[serde(default)]
+    pub agent_id: String,
+    #[serde(default)]
+    pub queue_id: String,
+    #[serde(default)]
     pub rank_before: u32,
+    #[serde(default)]
     pub rank_after: u32,
+    #[serde(default)]
     pub rr_before: u32,
+    #[serde(default)]
     pub rr_after: u32,
+    #[serde(default)]
     pub rr_change: i32,
     pub timestamp: u64,
+    #[serde(default)]
     pub kills: u32,
+    #[serde(default)]
     pub deaths: u32,
+    #[serde(default)]
     pub assists: u32,
+    #[serde(default)]
     pub score: u32,
+    #[serde(default)]
     pub rounds_won: u32,
+    #[serde(default)]
     pub rounds_lost: u32,
 }
 
@@ -106,14 +122,33 @@ pub struct AgentInfo {
     pub unlocked: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PregameState {
     pub match_id: String,
     pub map_id: String,
     pub map_name: String,
+    pub queue_id: String,
+    pub phase: String,
+    pub phase_time_remaining_ms: u64,
+    pub team_id: String,
     pub locked: bool,
     pub locked_agent: Option<String>,
+    pub selected_agent: Option<String>,
+    pub allies: Vec<PregamePlayer>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PregamePlayer {
+    pub puuid: String,
+    pub character_id: Option<String>,
+    pub selection_state: String,
+    pub is_self: bool,
+    pub is_captain: bool,
+    pub competitive_tier: u32,
+    pub account_level: u32,
+    pub incognito: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -133,7 +168,10 @@ pub struct CurrentMatch {
     pub match_id: String,
     pub players: Vec<MatchPlayer>,
     pub map_id: String,
+    pub map_name: String,
     pub queue_id: String,
+    pub provisioning_flow: String,
+    pub phase: String,
     pub is_ranked: bool,
 }
 
@@ -148,7 +186,7 @@ pub struct Friend {
     pub player_card_id: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PartyMember {
     pub puuid: String,
@@ -173,7 +211,7 @@ pub struct PartyInvite {
     pub from_tag: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PartyState {
     pub party_id: String,
@@ -187,7 +225,7 @@ pub struct PartyState {
     pub invites: Vec<PartyInvite>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LiveMatchPlayer {
     pub puuid: String,
@@ -203,6 +241,35 @@ pub struct LiveMatchPlayer {
     pub account_level: u32,
     pub incognito: bool,
     pub is_self: bool,
+    pub party_group: Option<u32>,
+    pub encounters: Option<EncounterSummary>,
+    pub watch_tags: Vec<String>,
+    pub recent_form: Option<RecentForm>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentForm {
+    pub matches: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub win_rate: f32,
+    pub kd: f32,
+    pub hs_percent: f32,
+    pub top_agents: Vec<String>,
+    pub rr_trend: Vec<i32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncounterSummary {
+    pub times_seen: u32,
+    pub as_teammate: u32,
+    pub as_enemy: u32,
+    pub last_seen: u64,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -216,4 +283,92 @@ pub struct LiveMatch {
     pub is_team_mode: bool,
     pub ally_team: Vec<LiveMatchPlayer>,
     pub enemy_team: Vec<LiveMatchPlayer>,
+    pub team_summary: Option<TeamSummary>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamAggregate {
+    pub players: u32,
+    pub ranked_players: u32,
+    pub avg_rank: f32,
+    pub median_rank: f32,
+    pub avg_peak_rank: f32,
+    pub min_level: u32,
+    pub max_level: u32,
+    pub level_spread: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamSummary {
+    pub ally: TeamAggregate,
+    pub enemy: Option<TeamAggregate>,
+    pub win_probability: Option<f32>,
+    pub calibration_matches: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadoutItem {
+    pub id: String,
+    pub name: String,
+    pub icon: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeaponLoadout {
+    pub weapon_id: String,
+    pub weapon_name: String,
+    pub skin: Option<LoadoutItem>,
+    pub buddy: Option<LoadoutItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerLoadout {
+    pub puuid: String,
+    pub team_id: String,
+    pub agent_id: String,
+    pub weapons: Vec<WeaponLoadout>,
+    pub sprays: Vec<LoadoutItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchLoadouts {
+    pub match_id: String,
+    pub phase: String,
+    pub players: Vec<PlayerLoadout>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LivePresence {
+    pub puuid: String,
+    pub game_name: String,
+    pub tag_line: String,
+    pub is_self: bool,
+    pub session_loop_state: String,
+    pub map_id: String,
+    pub map_name: String,
+    pub queue_id: String,
+    pub ally_score: u32,
+    pub enemy_score: u32,
+    pub party_id: String,
+    pub party_state: String,
+    pub party_size: u32,
+    pub max_party_size: u32,
+    pub is_party_owner: bool,
+    pub in_party: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PenaltyRestrictions {
+    pub active_penalties: u32,
+    pub infractions: u32,
+    pub queue_restricted: bool,
+    pub expires_at: Option<u64>,
 }
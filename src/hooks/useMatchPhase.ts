@@ -0,0 +1,36 @@
+import { createSignal, onMount, onCleanup } from "solid-js";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import { getMatchPhase } from "../ipc/commands";
+import type { PhaseSnapshot, PhaseTransition } from "../types/valorant";
+
+export function useMatchPhase(onTransition?: (transition: PhaseTransition) => void) {
+  const [phase, setPhase] = createSignal<PhaseSnapshot | null>(null);
+
+  let unlisten: UnlistenFn | null = null;
+  let disposed = false;
+
+  onMount(async () => {
+    getMatchPhase().then(setPhase).catch(() => {});
+    const fn = await listen<PhaseTransition>("match-phase", (event) => {
+      const t = event.payload;
+      setPhase({
+        phase: t.to,
+        matchId: t.matchId,
+        mapId: t.mapId,
+        mapName: t.mapName,
+        queueId: t.queueId,
+        since: t.timestamp,
+      });
+      onTransition?.(t);
+    });
+    if (disposed) fn();
+    else unlisten = fn;
+  });
+
+  onCleanup(() => {
+    disposed = true;
+    unlisten?.();
+  });
+
+  return phase;
+}
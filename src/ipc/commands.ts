@@ -1,5 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { ConnectionState, PlayerProfile, AgentInfo, PregameState, CurrentMatch, LiveMatch, PartyState, Friend } from "../types/valorant";
+import type { ConnectionState, PlayerProfile, AgentInfo, PregameState, CurrentMatch, LiveMatch, MatchLoadouts, PartyState, Friend, WatchEntry, PhaseSnapshot, LivePresence, AutomationStatus, DodgeReport, AuditEntry } from "../types/valorant";
 
 export async function getConnectionState(): Promise<ConnectionState> {
   return invoke<ConnectionState>("get_connection_state");
@@ -21,34 +21,133 @@ export async function instalockAgent(matchId: string, agentId: string): Promise<
   return invoke<void>("instalock_agent", { matchId, agentId });
 }
 
+export async function selectAgent(matchId: string, agentId: string): Promise<void> {
+  return invoke<void>("select_agent", { matchId, agentId });
+}
+
 export async function dodgeMatch(matchId: string): Promise<void> {
   return invoke<void>("dodge_match", { matchId });
 }
 
+export type TimingPreset = "instant" | "humanized" | "custom" | "deadline";
+
+export type DelayDistribution =
+  | { kind: "fixed"; ms: number }
+  | { kind: "uniform"; min: number; max: number }
+  | { kind: "normal"; mean: number; stdDev: number; min: number; max: number };
+
+export interface TimingConfig {
+  preset: TimingPreset;
+  select: DelayDistribution;
+  lock: DelayDistribution;
+  minGap: number;
+  deadlineSecs: number;
+}
+
+export type DodgeCondition =
+  | { kind: "map"; maps: string[] }
+  | { kind: "queue"; queues: string[] }
+  | { kind: "watchlisted"; tags: string[] }
+  | { kind: "duplicateRole"; roles: string[] }
+  | { kind: "allyRankBelow"; tier: number };
+
+export interface DodgeRule {
+  id: string;
+  name: string;
+  enabled: boolean;
+  conditions: DodgeCondition[];
+}
+
+export interface DodgeCap {
+  maxAutoDodges: number;
+  dodgeWindowHours: number;
+}
+
+export interface HoverMode {
+  hoverOnly: boolean;
+  autoLock: boolean;
+  autoLockSecs: number;
+}
+
+export interface QueueProfile {
+  instalock: boolean;
+  dodge: boolean;
+  agentPriority: string[];
+  mapPriority: Record<string, string[]>;
+}
+
+export interface AuditLogConfig {
+  enabled: boolean;
+  maxEntries: number;
+  retentionDays: number;
+}
+
+export interface ScheduleWindow {
+  days: string[];
+  start: string;
+  end: string;
+}
+
+export interface ProfileActivation {
+  enabled: boolean;
+  timezone: string;
+  windows: ScheduleWindow[];
+  partyWith: string[];
+}
+
+export interface ProfileSwitch {
+  from: string | null;
+  to: string;
+  name: string;
+  reason: string;
+}
+
+export interface Profile {
+  id: string;
+  name: string;
+  instalock: AppConfig["instalock"];
+  mapDodge: AppConfig["mapDodge"];
+  timing: TimingConfig;
+  discord: AppConfig["discord"];
+  activation: ProfileActivation;
+}
+
 export interface AppConfig {
   instalock: {
     active: boolean;
-    defaultAgent: string | null;
-    mapOverrides: Record<string, string>;
+    agentPriority: string[];
+    mapPriority: Record<string, string[]>;
+    hoverOnly: boolean;
+    autoLock: boolean;
+    autoLockSecs: number;
   };
   mapDodge: {
     active: boolean;
     blacklistedMaps: string[];
+    rules: DodgeRule[];
+    testMode: boolean;
+    maxAutoDodges: number;
+    dodgeWindowHours: number;
   };
-  timing: {
-    preset: string;
-    selectDelay: number;
-    lockDelay: number;
-  };
+  timing: TimingConfig;
   app: {
     minimizeOnClose: boolean;
     startMinimized: boolean;
+    postMatchNotifications: boolean;
   };
   discord: {
     enabled: boolean;
     details: string;
     state: string;
+    showMatchScore: boolean;
   };
+  queues: Record<string, QueueProfile>;
+  audit: AuditLogConfig;
+  automation: {
+    dryRun: boolean;
+  };
+  profiles: Profile[];
+  activeProfile: string | null;
 }
 
 export async function loadConfig(): Promise<AppConfig> {
@@ -59,10 +158,62 @@ export async function saveConfig(cfg: AppConfig): Promise<void> {
   return invoke<void>("save_config", { cfg });
 }
 
+export async function getDodgePenalties(): Promise<DodgeReport> {
+  return invoke<DodgeReport>("get_dodge_penalties");
+}
+
 export async function getLiveMatch(): Promise<LiveMatch | null> {
   return invoke<LiveMatch | null>("get_live_match");
 }
 
+export async function getMatchPhase(): Promise<PhaseSnapshot> {
+  return invoke<PhaseSnapshot>("get_match_phase");
+}
+
+export async function getLivePresences(): Promise<LivePresence[]> {
+  return invoke<LivePresence[]>("get_live_presences");
+}
+
+export async function createProfile(name: string): Promise<AppConfig> {
+  return invoke<AppConfig>("create_profile", { name });
+}
+
+export async function cloneProfile(id: string, name?: string): Promise<AppConfig> {
+  return invoke<AppConfig>("clone_profile", { id, name: name ?? null });
+}
+
+export async function deleteProfile(id: string): Promise<AppConfig> {
+  return invoke<AppConfig>("delete_profile", { id });
+}
+
+export async function setProfileActivation(id: string, activation: ProfileActivation): Promise<AppConfig> {
+  return invoke<AppConfig>("set_profile_activation", { id, activation });
+}
+
+export async function activateProfile(id: string): Promise<AppConfig> {
+  return invoke<AppConfig>("activate_profile", { id });
+}
+
+export async function getAutomationStatus(): Promise<AutomationStatus> {
+  return invoke<AutomationStatus>("get_automation_status");
+}
+
+export async function replayPregame(pregame?: PregameState): Promise<AutomationStatus> {
+  return invoke<AutomationStatus>("replay_pregame", { pregame: pregame ?? null });
+}
+
+export async function getReplayStatus(): Promise<AutomationStatus> {
+  return invoke<AutomationStatus>("get_replay_status");
+}
+
+export async function getAutomationLog(limit?: number): Promise<AuditEntry[]> {
+  return invoke<AuditEntry[]>("get_automation_log", { limit });
+}
+
+export async function getMatchLoadouts(): Promise<MatchLoadouts | null> {
+  return invoke<MatchLoadouts | null>("get_match_loadouts");
+}
+
 export async function getParty(): Promise<PartyState | null> {
   return invoke<PartyState | null>("get_party");
 }
@@ -122,3 +273,36 @@ export async function minimizeToTray(): Promise<void> {
 export async function getCurrentMatch(): Promise<CurrentMatch | null> {
   return invoke<CurrentMatch | null>("get_current_match");
 }
+
+export async function renderMatchCard(matchId: string, copy: boolean): Promise<string> {
+  return invoke<string>("render_match_card", { matchId, copy });
+}
+
+export type ExportFormat = "csv" | "json";
+
+export interface ExportFilter {
+  from?: number | null;
+  to?: number | null;
+  queue?: string | null;
+  map?: string | null;
+}
+
+export async function exportMatches(format: ExportFormat, filter: ExportFilter): Promise<string> {
+  return invoke<string>("export_matches", { format, filter });
+}
+
+export async function exportSessions(format: ExportFormat, filter: ExportFilter): Promise<string> {
+  return invoke<string>("export_sessions", { format, filter });
+}
+
+export async function getWatchlist(): Promise<WatchEntry[]> {
+  return invoke<WatchEntry[]>("get_watchlist");
+}
+
+export async function setWatchlistEntry(entry: WatchEntry): Promise<WatchEntry> {
+  return invoke<WatchEntry>("set_watchlist_entry", { entry });
+}
+
+export async function removeWatchlistEntry(puuid: string): Promise<void> {
+  return invoke<void>("remove_watchlist_entry", { puuid });
+}
@@ -32,6 +32,8 @@ export interface PlayerMMR {
 export interface CompUpdate {
   matchId: string;
   mapId: string;
+  agentId: string;
+  queueId: string;
   rankBefore: number;
   rankAfter: number;
   rrBefore: number;
@@ -66,8 +68,25 @@ export interface PregameState {
   matchId: string;
   mapId: string;
   mapName: string;
+  queueId: string;
+  phase: string;
+  phaseTimeRemainingMs: number;
+  teamId: string;
   locked: boolean;
   lockedAgent: string | null;
+  selectedAgent: string | null;
+  allies: PregamePlayer[];
+}
+
+export interface PregamePlayer {
+  puuid: string;
+  characterId: string | null;
+  selectionState: "" | "selected" | "locked";
+  isSelf: boolean;
+  isCaptain: boolean;
+  competitiveTier: number;
+  accountLevel: number;
+  incognito: boolean;
 }
 
 export interface MatchPlayer {
@@ -83,7 +102,10 @@ export interface CurrentMatch {
   matchId: string;
   players: MatchPlayer[];
   mapId: string;
+  mapName: string;
   queueId: string;
+  provisioningFlow: string;
+  phase: "pregame" | "ingame";
   isRanked: boolean;
 }
 
@@ -101,6 +123,56 @@ export interface LiveMatchPlayer {
   accountLevel: number;
   incognito: boolean;
   isSelf: boolean;
+  partyGroup: number | null;
+  encounters: EncounterSummary | null;
+  watchTags: string[];
+  recentForm: RecentForm | null;
+}
+
+export interface RecentForm {
+  matches: number;
+  wins: number;
+  losses: number;
+  winRate: number;
+  kd: number;
+  hsPercent: number;
+  topAgents: string[];
+  rrTrend: number[];
+}
+
+export interface WatchEntry {
+  puuid: string;
+  gameName: string;
+  tagLine: string;
+  tags: string[];
+  note: string;
+  addedAt: number;
+}
+
+export interface WatchAlertPlayer {
+  puuid: string;
+  gameName: string;
+  tagLine: string;
+  tags: string[];
+  note: string;
+  isEnemy: boolean;
+}
+
+export interface WatchAlert {
+  matchId: string;
+  phase: string;
+  mapName: string;
+  players: WatchAlertPlayer[];
+}
+
+export interface EncounterSummary {
+  timesSeen: number;
+  asTeammate: number;
+  asEnemy: number;
+  lastSeen: number;
+  wins: number;
+  losses: number;
+  draws: number;
 }
 
 export interface Friend {
@@ -154,4 +226,179 @@ export interface LiveMatch {
   isTeamMode: boolean;
   allyTeam: LiveMatchPlayer[];
   enemyTeam: LiveMatchPlayer[];
+  teamSummary: TeamSummary | null;
+}
+
+export interface TeamAggregate {
+  players: number;
+  rankedPlayers: number;
+  avgRank: number;
+  medianRank: number;
+  avgPeakRank: number;
+  minLevel: number;
+  maxLevel: number;
+  levelSpread: number;
+}
+
+export interface TeamSummary {
+  ally: TeamAggregate;
+  enemy: TeamAggregate | null;
+  winProbability: number | null;
+  calibrationMatches: number;
+}
+
+export interface LoadoutItem {
+  id: string;
+  name: string;
+  icon: string;
+}
+
+export interface WeaponLoadout {
+  weaponId: string;
+  weaponName: string;
+  skin: LoadoutItem | null;
+  buddy: LoadoutItem | null;
+}
+
+export interface PlayerLoadout {
+  puuid: string;
+  teamId: string;
+  agentId: string;
+  weapons: WeaponLoadout[];
+  sprays: LoadoutItem[];
+}
+
+export interface MatchLoadouts {
+  matchId: string;
+  phase: string;
+  players: PlayerLoadout[];
+}
+
+export type MatchPhase = "Menus" | "Queueing" | "MatchFound" | "Pregame" | "InGame" | "PostGame";
+
+export interface PhaseSnapshot {
+  phase: MatchPhase;
+  matchId: string;
+  mapId: string;
+  mapName: string;
+  queueId: string;
+  since: number;
+}
+
+export interface PhaseTransition {
+  from: MatchPhase;
+  to: MatchPhase;
+  matchId: string;
+  mapId: string;
+  mapName: string;
+  queueId: string;
+  finishedMatchId: string;
+  timestamp: number;
+}
+
+export interface LivePresence {
+  puuid: string;
+  gameName: string;
+  tagLine: string;
+  isSelf: boolean;
+  sessionLoopState: string;
+  mapId: string;
+  mapName: string;
+  queueId: string;
+  allyScore: number;
+  enemyScore: number;
+  partyId: string;
+  partyState: string;
+  partySize: number;
+  maxPartySize: number;
+  isPartyOwner: boolean;
+  inParty: boolean;
+}
+
+export interface PresenceChange {
+  presence: LivePresence;
+  scoreChanged: boolean;
+}
+
+export type InstalockState = "idle" | "waiting" | "disabled" | "hovering" | "locking" | "locked" | "failed";
+
+export interface InstalockStatus {
+  state: InstalockState;
+  matchId: string;
+  mapName: string;
+  queueId: string;
+  agentId: string | null;
+  message: string;
+}
+
+export type DodgeState = "idle" | "waiting" | "disabled" | "safe" | "test" | "capped" | "dodging" | "dodged" | "failed";
+
+export interface RuleMatch {
+  ruleId: string;
+  rule: string;
+  reasons: string[];
+}
+
+export interface DodgeStatus {
+  state: DodgeState;
+  matchId: string;
+  mapName: string;
+  queueId: string;
+  rule: RuleMatch | null;
+  message: string;
+}
+
+export interface AutomationStatus {
+  instalock: InstalockStatus;
+  dodge: DodgeStatus;
+  dryRun: boolean;
+}
+
+export interface PenaltyRestrictions {
+  activePenalties: number;
+  infractions: number;
+  queueRestricted: boolean;
+  expiresAt: number | null;
+}
+
+export interface DodgeRecord {
+  matchId: string;
+  timestamp: number;
+  mapName: string;
+  queueId: string;
+  automatic: boolean;
+  rule: string;
+}
+
+export interface PenaltyEstimate {
+  dodges24h: number;
+  tier: number;
+  nextTimeoutMinutes: number;
+  nextRrLoss: number;
+}
+
+export interface DodgeReport {
+  recent: DodgeRecord[];
+  estimate: PenaltyEstimate;
+  autoDodgesInWindow: number;
+  maxAutoDodges: number;
+  windowHours: number;
+  restrictions: PenaltyRestrictions | null;
+}
+
+export interface AuditEntry {
+  timestamp: number;
+  feature: "instalock" | "dodge";
+  action: string;
+  matchId: string;
+  mapName: string;
+  queueId: string;
+  rule: string;
+  agentId: string | null;
+  httpStatus: number | null;
+  success: boolean;
+  message: string;
+  delayMs: number;
+  durationMs: number;
+  phaseRemainingMs: number;
 }
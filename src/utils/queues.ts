@@ -0,0 +1,33 @@
+import type { QueueProfile } from "../ipc/commands";
+
+export const QUEUES: { id: string; label: string }[] = [
+  { id: "competitive", label: "Competitive" },
+  { id: "unrated", label: "Unrated" },
+  { id: "swiftplay", label: "Swiftplay" },
+  { id: "premier", label: "Premier" },
+  { id: "spikerush", label: "Spike Rush" },
+  { id: "deathmatch", label: "Deathmatch" },
+  { id: "hurm", label: "Team Deathmatch" },
+  { id: "custom", label: "Custom" },
+];
+
+export function queueKey(queueId: string): string {
+  return queueId || "custom";
+}
+
+export function queueLabel(queueId: string): string {
+  const key = queueKey(queueId);
+  return QUEUES.find(q => q.id === key)?.label ?? key;
+}
+
+export function queueProfile(queues: Record<string, QueueProfile>, queueId: string): QueueProfile {
+  return { instalock: true, dodge: true, agentPriority: [], mapPriority: {}, ...queues[queueKey(queueId)] };
+}
+
+export function withQueueProfile(
+  queues: Record<string, QueueProfile>,
+  queueId: string,
+  patch: Partial<QueueProfile>,
+): Record<string, QueueProfile> {
+  return { ...queues, [queueKey(queueId)]: { ...queueProfile(queues, queueId), ...patch } };
+}
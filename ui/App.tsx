@@ -1,7 +1,8 @@
-import { Component, createSignal, Switch, Match, onMount } from "solid-js";
+import { Component, createSignal, Switch, Match, onMount, onCleanup } from "solid-js";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import { useValorant } from "@src/hooks/useValorant";
 import { loadConfig, saveConfig } from "@src/ipc/commands";
-import type { AppConfig } from "@src/ipc/commands";
+import type { AppConfig, QueueProfile, TimingConfig, HoverMode, DodgeRule, DodgeCap, AuditLogConfig, Profile } from "@src/ipc/commands";
 import Titlebar from "./components/Titlebar";
 import Sidebar from "./components/Sidebar";
 import Dashboard from "./pages/Dashboard";
@@ -10,95 +11,162 @@ import MapDodge from "./pages/MapDodge";
 import LiveMatch from "./pages/LiveMatch";
 import Party from "./pages/Party";
 import Settings from "./pages/Settings";
-import type { InstalockTiming } from "./pages/Settings";
 
 const App: Component = () => {
   const { status, playerInfo } = useValorant();
   const [activeTab, setActiveTab] = createSignal("dashboard");
-  const [instalockTiming, setInstalockTiming] = createSignal<InstalockTiming>({
+  const [instalockTiming, setInstalockTiming] = createSignal<TimingConfig>({
     preset: "instant",
-    selectDelay: 0,
-    lockDelay: 0,
+    select: { kind: "fixed", ms: 0 },
+    lock: { kind: "fixed", ms: 0 },
+    minGap: 0,
+    deadlineSecs: 5,
   });
   const [instalockActive, setInstalockActive] = createSignal(false);
-  const [instalockAgent, setInstalockAgent] = createSignal<string | null>(null);
-  const [instalockOverrides, setInstalockOverrides] = createSignal<Record<string, string>>({});
+  const [instalockPriority, setInstalockPriority] = createSignal<string[]>([]);
+  const [instalockMapPriority, setInstalockMapPriority] = createSignal<Record<string, string[]>>({});
+  const [hoverMode, setHoverMode] = createSignal<HoverMode>({ hoverOnly: false, autoLock: false, autoLockSecs: 5 });
   const [dodgeActive, setDodgeActive] = createSignal(false);
   const [dodgeBlacklist, setDodgeBlacklist] = createSignal<string[]>([]);
+  const [dodgeRules, setDodgeRules] = createSignal<DodgeRule[]>([]);
+  const [dodgeTestMode, setDodgeTestMode] = createSignal(false);
+  const [dodgeCap, setDodgeCap] = createSignal<DodgeCap>({ maxAutoDodges: 0, dodgeWindowHours: 24 });
+  const [queueProfiles, setQueueProfiles] = createSignal<Record<string, QueueProfile>>({});
+  const [dryRun, setDryRun] = createSignal(false);
+  const [auditLog, setAuditLog] = createSignal<AuditLogConfig>({ enabled: true, maxEntries: 2000, retentionDays: 30 });
   const [minimizeOnClose, setMinimizeOnClose] = createSignal(false);
   const [startMinimized, setStartMinimized] = createSignal(false);
+  const [postMatchNotifications, setPostMatchNotifications] = createSignal(true);
   const [discordEnabled, setDiscordEnabled] = createSignal(true);
   const [discordDetails, setDiscordDetails] = createSignal("Playing Valorant with Downfall");
   const [discordState, setDiscordState] = createSignal("");
+  const [discordShowScore, setDiscordShowScore] = createSignal(true);
+  const [profiles, setProfiles] = createSignal<Profile[]>([]);
+  const [activeProfile, setActiveProfile] = createSignal<string | null>(null);
   const [configLoaded, setConfigLoaded] = createSignal(false);
 
   let saveTimeout: ReturnType<typeof setTimeout> | null = null;
+  const pendingEdits = new Map<unknown, () => void>();
+  let unlistenConfig: UnlistenFn | null = null;
+  let unlistenNavigate: UnlistenFn | null = null;
 
   const buildConfig = (): AppConfig => ({
     instalock: {
       active: instalockActive(),
-      defaultAgent: instalockAgent(),
-      mapOverrides: instalockOverrides(),
+      agentPriority: instalockPriority(),
+      mapPriority: instalockMapPriority(),
+      ...hoverMode(),
     },
     mapDodge: {
       active: dodgeActive(),
       blacklistedMaps: dodgeBlacklist(),
+      rules: dodgeRules(),
+      testMode: dodgeTestMode(),
+      ...dodgeCap(),
     },
-    timing: {
-      preset: instalockTiming().preset,
-      selectDelay: instalockTiming().selectDelay,
-      lockDelay: instalockTiming().lockDelay,
-    },
+    timing: instalockTiming(),
     app: {
       minimizeOnClose: minimizeOnClose(),
       startMinimized: startMinimized(),
+      postMatchNotifications: postMatchNotifications(),
     },
     discord: {
       enabled: discordEnabled(),
       details: discordDetails(),
       state: discordState(),
+      showMatchScore: discordShowScore(),
+    },
+    queues: queueProfiles(),
+    audit: auditLog(),
+    automation: {
+      dryRun: dryRun(),
     },
+    profiles: profiles(),
+    activeProfile: activeProfile(),
   });
 
-  const debouncedSave = () => {
+  const debouncedSave = (key: unknown, reapply: () => void) => {
+    pendingEdits.set(key, reapply);
     if (saveTimeout) clearTimeout(saveTimeout);
     saveTimeout = setTimeout(() => {
+      saveTimeout = null;
+      pendingEdits.clear();
       saveConfig(buildConfig()).catch(() => {});
     }, 500);
   };
 
-  const updateInstalockActive = (v: boolean) => { setInstalockActive(v); debouncedSave(); };
-  const updateInstalockAgent = (v: string | null) => { setInstalockAgent(v); debouncedSave(); };
-  const updateInstalockOverrides = (v: Record<string, string>) => { setInstalockOverrides(v); debouncedSave(); };
-  const updateDodgeActive = (v: boolean) => { setDodgeActive(v); debouncedSave(); };
-  const updateDodgeBlacklist = (v: string[]) => { setDodgeBlacklist(v); debouncedSave(); };
-  const updateTiming = (v: InstalockTiming) => { setInstalockTiming(v); debouncedSave(); };
-  const updateMinimizeOnClose = (v: boolean) => { setMinimizeOnClose(v); debouncedSave(); };
-  const updateStartMinimized = (v: boolean) => { setStartMinimized(v); debouncedSave(); };
-  const updateDiscordEnabled = (v: boolean) => { setDiscordEnabled(v); debouncedSave(); };
-  const updateDiscordDetails = (v: string) => { setDiscordDetails(v); debouncedSave(); };
-  const updateDiscordState = (v: string) => { setDiscordState(v); debouncedSave(); };
+  const updateInstalockActive = (v: boolean) => { setInstalockActive(v); debouncedSave(setInstalockActive, () => setInstalockActive(v)); };
+  const updateInstalockPriority = (v: string[]) => { setInstalockPriority(v); debouncedSave(setInstalockPriority, () => setInstalockPriority(v)); };
+  const updateInstalockMapPriority = (v: Record<string, string[]>) => { setInstalockMapPriority(v); debouncedSave(setInstalockMapPriority, () => setInstalockMapPriority(v)); };
+  const updateHoverMode = (v: HoverMode) => { setHoverMode(v); debouncedSave(setHoverMode, () => setHoverMode(v)); };
+  const updateDodgeActive = (v: boolean) => { setDodgeActive(v); debouncedSave(setDodgeActive, () => setDodgeActive(v)); };
+  const updateDodgeBlacklist = (v: string[]) => { setDodgeBlacklist(v); debouncedSave(setDodgeBlacklist, () => setDodgeBlacklist(v)); };
+  const updateDodgeRules = (v: DodgeRule[]) => { setDodgeRules(v); debouncedSave(setDodgeRules, () => setDodgeRules(v)); };
+  const updateDodgeTestMode = (v: boolean) => { setDodgeTestMode(v); debouncedSave(setDodgeTestMode, () => setDodgeTestMode(v)); };
+  const updateDodgeCap = (v: DodgeCap) => { setDodgeCap(v); debouncedSave(setDodgeCap, () => setDodgeCap(v)); };
+  const updateQueueProfiles = (v: Record<string, QueueProfile>) => { setQueueProfiles(v); debouncedSave(setQueueProfiles, () => setQueueProfiles(v)); };
+  const updateTiming = (v: TimingConfig) => { setInstalockTiming(v); debouncedSave(setInstalockTiming, () => setInstalockTiming(v)); };
+  const updateDryRun = (v: boolean) => { setDryRun(v); debouncedSave(setDryRun, () => setDryRun(v)); };
+  const updateAuditLog = (v: AuditLogConfig) => { setAuditLog(v); debouncedSave(setAuditLog, () => setAuditLog(v)); };
+  const updateMinimizeOnClose = (v: boolean) => { setMinimizeOnClose(v); debouncedSave(setMinimizeOnClose, () => setMinimizeOnClose(v)); };
+  const updateStartMinimized = (v: boolean) => { setStartMinimized(v); debouncedSave(setStartMinimized, () => setStartMinimized(v)); };
+  const updatePostMatchNotifications = (v: boolean) => { setPostMatchNotifications(v); debouncedSave(setPostMatchNotifications, () => setPostMatchNotifications(v)); };
+  const updateDiscordEnabled = (v: boolean) => { setDiscordEnabled(v); debouncedSave(setDiscordEnabled, () => setDiscordEnabled(v)); };
+  const updateDiscordDetails = (v: string) => { setDiscordDetails(v); debouncedSave(setDiscordDetails, () => setDiscordDetails(v)); };
+  const updateDiscordState = (v: string) => { setDiscordState(v); debouncedSave(setDiscordState, () => setDiscordState(v)); };
+  const updateDiscordShowScore = (v: boolean) => { setDiscordShowScore(v); debouncedSave(setDiscordShowScore, () => setDiscordShowScore(v)); };
+
+  const applyConfig = (cfg: AppConfig) => {
+    setInstalockActive(cfg.instalock.active);
+    setInstalockPriority(cfg.instalock.agentPriority);
+    setInstalockMapPriority(cfg.instalock.mapPriority);
+    setHoverMode({
+      hoverOnly: cfg.instalock.hoverOnly ?? false,
+      autoLock: cfg.instalock.autoLock ?? false,
+      autoLockSecs: cfg.instalock.autoLockSecs ?? 5,
+    });
+    setDodgeActive(cfg.mapDodge.active);
+    setDodgeBlacklist(cfg.mapDodge.blacklistedMaps);
+    setDodgeRules(cfg.mapDodge.rules ?? []);
+    setDodgeTestMode(cfg.mapDodge.testMode ?? false);
+    setDodgeCap({
+      maxAutoDodges: cfg.mapDodge.maxAutoDodges ?? 0,
+      dodgeWindowHours: cfg.mapDodge.dodgeWindowHours ?? 24,
+    });
+    setQueueProfiles(cfg.queues ?? {});
+    setInstalockTiming(cfg.timing);
+    setDryRun(cfg.automation?.dryRun ?? false);
+    setAuditLog({
+      enabled: cfg.audit?.enabled ?? true,
+      maxEntries: cfg.audit?.maxEntries ?? 2000,
+      retentionDays: cfg.audit?.retentionDays ?? 30,
+    });
+    setMinimizeOnClose(cfg.app?.minimizeOnClose ?? false);
+    setStartMinimized(cfg.app?.startMinimized ?? false);
+    setPostMatchNotifications(cfg.app?.postMatchNotifications ?? true);
+    setDiscordEnabled(cfg.discord?.enabled ?? true);
+    setDiscordDetails(cfg.discord?.details ?? "Playing Valorant with Downfall");
+    setDiscordState(cfg.discord?.state ?? "");
+    setDiscordShowScore(cfg.discord?.showMatchScore ?? true);
+    setProfiles(cfg.profiles ?? []);
+    setActiveProfile(cfg.activeProfile ?? null);
+  };
 
   onMount(async () => {
     try {
-      const cfg = await loadConfig();
-      setInstalockActive(cfg.instalock.active);
-      setInstalockAgent(cfg.instalock.defaultAgent);
-      setInstalockOverrides(cfg.instalock.mapOverrides);
-      setDodgeActive(cfg.mapDodge.active);
-      setDodgeBlacklist(cfg.mapDodge.blacklistedMaps);
-      setInstalockTiming({
-        preset: cfg.timing.preset as InstalockTiming["preset"],
-        selectDelay: cfg.timing.selectDelay,
-        lockDelay: cfg.timing.lockDelay,
-      });
-      setMinimizeOnClose(cfg.app?.minimizeOnClose ?? false);
-      setStartMinimized(cfg.app?.startMinimized ?? false);
-      setDiscordEnabled(cfg.discord?.enabled ?? true);
-      setDiscordDetails(cfg.discord?.details ?? "Playing Valorant with Downfall");
-      setDiscordState(cfg.discord?.state ?? "");
+      applyConfig(await loadConfig());
     } catch {}
     setConfigLoaded(true);
+    unlistenConfig = await listen<AppConfig>("config-changed", (event) => {
+      applyConfig(event.payload);
+      pendingEdits.forEach(reapply => reapply());
+    });
+    unlistenNavigate = await listen<string>("navigate", (event) => setActiveTab(event.payload));
+  });
+
+  onCleanup(() => {
+    unlistenConfig?.();
+    unlistenNavigate?.();
   });
 
   return (
@@ -116,13 +184,16 @@ const App: Component = () => {
             <Match when={activeTab() === "instalock"}>
               <InstaLock
                 status={status()}
-                instalockTiming={instalockTiming()}
                 active={instalockActive()}
                 onActiveChange={updateInstalockActive}
-                selectedAgent={instalockAgent()}
-                onAgentChange={updateInstalockAgent}
-                mapOverrides={instalockOverrides()}
-                onOverridesChange={updateInstalockOverrides}
+                agentPriority={instalockPriority()}
+                onPriorityChange={updateInstalockPriority}
+                mapPriority={instalockMapPriority()}
+                onMapPriorityChange={updateInstalockMapPriority}
+                hoverMode={hoverMode()}
+                onHoverModeChange={updateHoverMode}
+                queues={queueProfiles()}
+                onQueuesChange={updateQueueProfiles}
                 configLoaded={configLoaded()}
               />
             </Match>
@@ -133,6 +204,14 @@ const App: Component = () => {
                 onActiveChange={updateDodgeActive}
                 blacklist={dodgeBlacklist()}
                 onBlacklistChange={updateDodgeBlacklist}
+                rules={dodgeRules()}
+                onRulesChange={updateDodgeRules}
+                testMode={dodgeTestMode()}
+                onTestModeChange={updateDodgeTestMode}
+                dodgeCap={dodgeCap()}
+                onDodgeCapChange={updateDodgeCap}
+                queues={queueProfiles()}
+                onQueuesChange={updateQueueProfiles}
               />
             </Match>
             <Match when={activeTab() === "livematch"}>
@@ -145,16 +224,26 @@ const App: Component = () => {
               <Settings
                 instalockTiming={instalockTiming()}
                 onTimingChange={updateTiming}
+                profiles={profiles()}
+                activeProfile={activeProfile()}
+                dryRun={dryRun()}
+                onDryRunChange={updateDryRun}
+                auditLog={auditLog()}
+                onAuditLogChange={updateAuditLog}
                 minimizeOnClose={minimizeOnClose()}
                 onMinimizeOnCloseChange={updateMinimizeOnClose}
                 startMinimized={startMinimized()}
                 onStartMinimizedChange={updateStartMinimized}
+                postMatchNotifications={postMatchNotifications()}
+                onPostMatchNotificationsChange={updatePostMatchNotifications}
                 discordEnabled={discordEnabled()}
                 onDiscordEnabledChange={updateDiscordEnabled}
                 discordDetails={discordDetails()}
                 onDiscordDetailsChange={updateDiscordDetails}
                 discordState={discordState()}
                 onDiscordStateChange={updateDiscordState}
+                discordShowScore={discordShowScore()}
+                onDiscordShowScoreChange={updateDiscordShowScore}
               />
             </Match>
           </Switch>
@@ -0,0 +1,55 @@
+import { Component, For, Show, createSignal, onMount, onCleanup } from "solid-js";
+import { listen } from "@tauri-apps/api/event";
+import { getAutomationLog } from "@src/ipc/commands";
+import type { AuditEntry } from "@src/types/valorant";
+import { queueLabel } from "@src/utils/queues";
+
+const formatTime = (ts: number) =>
+  new Date(ts).toLocaleString([], { month: "short", day: "numeric", hour: "2-digit", minute: "2-digit", second: "2-digit" });
+
+const AutomationLog: Component = () => {
+  const [entries, setEntries] = createSignal<AuditEntry[]>([]);
+
+  const refresh = () => getAutomationLog(100).then(setEntries).catch(() => {});
+
+  onMount(() => {
+    refresh();
+    const unlisten = listen("automation-status", refresh);
+    onCleanup(() => { unlisten.then(fn => fn()); });
+  });
+
+  return (
+    <div class="al-log">
+      <Show when={entries().length > 0} fallback={<span class="al-empty">No automated actions recorded yet</span>}>
+        <For each={entries()}>
+          {(e) => (
+            <div class={`al-entry ${e.success ? "" : "al-entry-failed"}`}>
+              <div class="al-entry-main">
+                <span class="al-entry-action">{e.feature} · {e.action}</span>
+                <span class="al-entry-context">
+                  {e.mapName || "Unknown map"} · {queueLabel(e.queueId)}
+                  <Show when={e.rule}> · {e.rule}</Show>
+                </span>
+                <Show when={e.message}>
+                  <span class="al-entry-message">{e.message}</span>
+                </Show>
+              </div>
+              <div class="al-entry-meta">
+                <span>{formatTime(e.timestamp)}</span>
+                <Show when={e.httpStatus !== null}>
+                  <span class="al-entry-status">HTTP {e.httpStatus}</span>
+                </Show>
+                <Show when={e.httpStatus !== null || e.delayMs > 0}>
+                  <span>{e.delayMs}ms wait · {e.durationMs}ms request</span>
+                </Show>
+                <span class="al-entry-match" title={e.matchId}>{e.matchId.slice(0, 8)}</span>
+              </div>
+            </div>
+          )}
+        </For>
+      </Show>
+    </div>
+  );
+};
+
+export default AutomationLog;
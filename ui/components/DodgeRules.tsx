@@ -0,0 +1,165 @@
+import { Component, Show, For } from "solid-js";
+import type { DodgeRule, DodgeCondition } from "@src/ipc/commands";
+import { QUEUES } from "@src/utils/queues";
+import { rankName } from "@src/utils/ranks";
+
+interface DodgeRulesProps {
+  maps: string[];
+  rules: DodgeRule[];
+  onChange: (v: DodgeRule[]) => void;
+}
+
+const ROLES = ["Duelist", "Initiator", "Controller", "Sentinel"];
+
+const CONDITION_LABELS: Record<DodgeCondition["kind"], string> = {
+  map: "Map is",
+  queue: "Queue is",
+  watchlisted: "Teammate on watchlist",
+  duplicateRole: "Duplicate role locked",
+  allyRankBelow: "Avg ally rank below",
+};
+
+const NEW_CONDITIONS: Record<DodgeCondition["kind"], DodgeCondition> = {
+  map: { kind: "map", maps: [] },
+  queue: { kind: "queue", queues: ["competitive"] },
+  watchlisted: { kind: "watchlisted", tags: [] },
+  duplicateRole: { kind: "duplicateRole", roles: [] },
+  allyRankBelow: { kind: "allyRankBelow", tier: 12 },
+};
+
+const toggle = (list: string[], value: string) =>
+  list.includes(value) ? list.filter(v => v !== value) : [...list, value];
+
+const Chips: Component<{ options: { id: string; label: string }[]; selected: string[]; onChange: (v: string[]) => void }> = (props) => (
+  <div class="dr-chips">
+    <For each={props.options}>
+      {(o) => (
+        <button
+          class={`dr-chip ${props.selected.includes(o.id) ? "dr-chip-active" : ""}`}
+          onClick={() => props.onChange(toggle(props.selected, o.id))}
+        >
+          {o.label}
+        </button>
+      )}
+    </For>
+  </div>
+);
+
+const ConditionEditor: Component<{ condition: DodgeCondition; maps: string[]; onChange: (c: DodgeCondition) => void; onRemove: () => void }> = (props) => {
+  const c = () => props.condition;
+
+  return (
+    <div class="dr-condition">
+      <div class="dr-condition-header">
+        <span class="dr-condition-label">{CONDITION_LABELS[c().kind]}</span>
+        <button class="dr-remove" onClick={props.onRemove}>×</button>
+      </div>
+      <Show when={c().kind === "map" && c()}>
+        {(m) => (
+          <Chips
+            options={props.maps.map(id => ({ id, label: id }))}
+            selected={m().maps}
+            onChange={(maps) => props.onChange({ kind: "map", maps })}
+          />
+        )}
+      </Show>
+      <Show when={c().kind === "queue" && c()}>
+        {(q) => <Chips options={QUEUES} selected={q().queues} onChange={(queues) => props.onChange({ kind: "queue", queues })} />}
+      </Show>
+      <Show when={c().kind === "watchlisted" && c()}>
+        {(w) => (
+          <input
+            type="text"
+            class="st-input"
+            placeholder="Any tag (comma separated to filter)"
+            value={w().tags.join(", ")}
+            onChange={(e) => props.onChange({ kind: "watchlisted", tags: e.currentTarget.value.split(",").map(t => t.trim()).filter(Boolean) })}
+          />
+        )}
+      </Show>
+      <Show when={c().kind === "duplicateRole" && c()}>
+        {(d) => (
+          <Chips
+            options={ROLES.map(id => ({ id, label: id }))}
+            selected={d().roles}
+            onChange={(roles) => props.onChange({ kind: "duplicateRole", roles })}
+          />
+        )}
+      </Show>
+      <Show when={c().kind === "allyRankBelow" && c()}>
+        {(r) => (
+          <select class="st-input" value={r().tier} onChange={(e) => props.onChange({ kind: "allyRankBelow", tier: parseInt(e.currentTarget.value) })}>
+            <For each={Array.from({ length: 25 }, (_, i) => i + 3)}>
+              {(tier) => <option value={tier}>{rankName(tier)}</option>}
+            </For>
+          </select>
+        )}
+      </Show>
+    </div>
+  );
+};
+
+const DodgeRules: Component<DodgeRulesProps> = (props) => {
+  const update = (id: string, patch: Partial<DodgeRule>) =>
+    props.onChange(props.rules.map(r => r.id === id ? { ...r, ...patch } : r));
+
+  const addRule = () => props.onChange([
+    ...props.rules,
+    { id: crypto.randomUUID(), name: `Rule ${props.rules.length + 1}`, enabled: true, conditions: [] },
+  ]);
+
+  return (
+    <div class="dr-rules">
+      <For each={props.rules}>
+        {(rule) => (
+          <div class={`dr-rule ${!rule.enabled ? "dr-rule-off" : ""}`}>
+            <div class="dr-rule-header">
+              <input
+                type="text"
+                class="dr-rule-name"
+                value={rule.name}
+                onChange={(e) => update(rule.id, { name: e.currentTarget.value })}
+              />
+              <select
+                class="st-input dr-add"
+                value=""
+                onChange={(e) => {
+                  const kind = e.currentTarget.value as DodgeCondition["kind"];
+                  e.currentTarget.value = "";
+                  if (kind) update(rule.id, { conditions: [...rule.conditions, NEW_CONDITIONS[kind]] });
+                }}
+              >
+                <option value="">+ Condition</option>
+                <For each={Object.entries(CONDITION_LABELS)}>
+                  {([kind, label]) => <option value={kind}>{label}</option>}
+                </For>
+              </select>
+              <button
+                class={`il-toggle ${rule.enabled ? "il-toggle-on" : ""}`}
+                onClick={() => update(rule.id, { enabled: !rule.enabled })}
+              >
+                <div class="il-toggle-knob" />
+              </button>
+              <button class="dr-remove" onClick={() => props.onChange(props.rules.filter(r => r.id !== rule.id))}>×</button>
+            </div>
+            <Show when={rule.conditions.length > 0} fallback={<span class="dr-empty">Add conditions — the rule fires when all of them match</span>}>
+              <For each={rule.conditions}>
+                {(condition, i) => (
+                  <ConditionEditor
+                    condition={condition}
+                    maps={props.maps}
+                    onChange={(c) => update(rule.id, { conditions: rule.conditions.map((x, j) => j === i() ? c : x) })}
+                    onRemove={() => update(rule.id, { conditions: rule.conditions.filter((_, j) => j !== i()) })}
+                  />
+                )}
+              </For>
+            </Show>
+          </div>
+        )}
+      </For>
+      <button class="il-filter-btn dr-new" onClick={addRule}>+ New Rule</button>
+    </div>
+  );
+};
+
+export default DodgeRules;
@@ -0,0 +1,77 @@
+import { Component, For, Show } from "solid-js";
+import type { ProfileActivation as Activation, ScheduleWindow } from "@src/ipc/commands";
+
+interface ProfileActivationProps {
+  activation: Activation;
+  onChange: (v: Activation) => void;
+}
+
+const DAYS = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+const toggle = (list: string[], value: string) =>
+  list.includes(value) ? list.filter(v => v !== value) : [...list, value];
+
+const ProfileActivation: Component<ProfileActivationProps> = (props) => {
+  const a = () => props.activation;
+  const update = (patch: Partial<Activation>) => props.onChange({ ...a(), ...patch });
+  const updateWindow = (i: number, patch: Partial<ScheduleWindow>) =>
+    update({ windows: a().windows.map((w, j) => j === i ? { ...w, ...patch } : w) });
+
+  return (
+    <div class="pa-editor">
+      <div class="pa-row">
+        <label class="pa-check">
+          <input type="checkbox" checked={a().enabled} onChange={(e) => update({ enabled: e.currentTarget.checked })} />
+          Switch to this profile automatically
+        </label>
+        <input
+          type="text"
+          class="st-input pa-timezone"
+          value={a().timezone}
+          onChange={(e) => update({ timezone: e.currentTarget.value.trim() })}
+          placeholder={`Timezone (${Intl.DateTimeFormat().resolvedOptions().timeZone})`}
+        />
+      </div>
+
+      <For each={a().windows}>
+        {(w, i) => (
+          <div class="pa-window">
+            <div class="dr-chips">
+              <For each={DAYS}>
+                {(day) => (
+                  <button
+                    class={`dr-chip ${w.days.includes(day) ? "dr-chip-active" : ""}`}
+                    onClick={() => updateWindow(i(), { days: toggle(w.days, day) })}
+                  >
+                    {day}
+                  </button>
+                )}
+              </For>
+            </div>
+            <input type="time" class="st-input" value={w.start} onChange={(e) => updateWindow(i(), { start: e.currentTarget.value })} />
+            <span class="pa-to">to</span>
+            <input type="time" class="st-input" value={w.end} onChange={(e) => updateWindow(i(), { end: e.currentTarget.value })} />
+            <button class="dr-remove" onClick={() => update({ windows: a().windows.filter((_, j) => j !== i()) })}>×</button>
+          </div>
+        )}
+      </For>
+      <button class="il-filter-btn" onClick={() => update({ windows: [...a().windows, { days: [], start: "18:00", end: "23:00" }] })}>
+        + Time Window
+      </button>
+
+      <input
+        type="text"
+        class="st-input"
+        value={a().partyWith.join(", ")}
+        onChange={(e) => update({ partyWith: e.currentTarget.value.split(",").map(v => v.trim()).filter(Boolean) })}
+        placeholder="Only when in a party with (Name#Tag, comma separated)"
+      />
+
+      <Show when={a().enabled && a().windows.length === 0 && a().partyWith.length === 0}>
+        <span class="dr-empty">Add a time window or party member — the profile switches in when all of them match</span>
+      </Show>
+    </div>
+  );
+};
+
+export default ProfileActivation;
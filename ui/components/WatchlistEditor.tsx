@@ -0,0 +1,102 @@
+import { Component, Show, For, createSignal } from "solid-js";
+import { TbOutlineTag } from "solid-icons/tb";
+import { setWatchlistEntry, removeWatchlistEntry } from "@src/ipc/commands";
+import type { WatchEntry } from "@src/types/valorant";
+
+interface WatchlistEditorProps {
+  puuid: string;
+  gameName: string;
+  tagLine: string;
+  entry: WatchEntry | null;
+  onChange: () => void;
+}
+
+const WatchlistEditor: Component<WatchlistEditorProps> = (props) => {
+  const [open, setOpen] = createSignal(false);
+  const [tags, setTags] = createSignal("");
+  const [note, setNote] = createSignal("");
+  const [saving, setSaving] = createSignal(false);
+
+  const toggle = () => {
+    if (!open()) {
+      setTags(props.entry?.tags.join(", ") ?? "");
+      setNote(props.entry?.note ?? "");
+    }
+    setOpen(!open());
+  };
+
+  const save = async () => {
+    setSaving(true);
+    try {
+      await setWatchlistEntry({
+        puuid: props.puuid,
+        gameName: props.gameName,
+        tagLine: props.tagLine,
+        tags: tags().split(",").map(t => t.trim()).filter(Boolean),
+        note: note().trim(),
+        addedAt: props.entry?.addedAt ?? 0,
+      });
+      setOpen(false);
+      props.onChange();
+    } catch {}
+    setSaving(false);
+  };
+
+  const remove = async () => {
+    setSaving(true);
+    try {
+      await removeWatchlistEntry(props.puuid);
+      setOpen(false);
+      props.onChange();
+    } catch {}
+    setSaving(false);
+  };
+
+  return (
+    <div class="wl-editor">
+      <button
+        class={`wl-toggle ${props.entry ? "wl-toggle-watched" : ""}`}
+        onClick={(e) => { e.stopPropagation(); toggle(); }}
+        title={props.entry ? "Edit watchlist entry" : "Add to watchlist"}
+      >
+        <TbOutlineTag size={12} />
+      </button>
+      <Show when={open()}>
+        <div class="wl-popover" onClick={(e) => e.stopPropagation()}>
+          <span class="wl-popover-title">{props.gameName || "Player"}</span>
+          <input
+            class="st-input"
+            type="text"
+            placeholder="Tags, comma separated"
+            value={tags()}
+            onInput={(e) => setTags(e.currentTarget.value)}
+          />
+          <textarea
+            class="st-input wl-note"
+            placeholder="Note"
+            value={note()}
+            onInput={(e) => setNote(e.currentTarget.value)}
+          />
+          <div class="wl-actions">
+            <Show when={props.entry}>
+              <button class="wl-remove" onClick={remove} disabled={saving()}>Remove</button>
+            </Show>
+            <button class="wl-save" onClick={save} disabled={saving()}>Save</button>
+          </div>
+        </div>
+      </Show>
+    </div>
+  );
+};
+
+export const WatchTags: Component<{ tags: string[] }> = (props) => (
+  <Show when={props.tags.length > 0}>
+    <span class="wl-tags">
+      <For each={props.tags}>
+        {(tag) => <span class="wl-tag">{tag}</span>}
+      </For>
+    </span>
+  </Show>
+);
+
+export default WatchlistEditor;
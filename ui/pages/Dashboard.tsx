@@ -1,6 +1,7 @@
-import { Component, Show, For, createSignal, createEffect, on } from "solid-js";
-import { TbOutlineRefresh } from "solid-icons/tb";
-import { getPlayerProfile } from "@src/ipc/commands";
+import { Component, Show, For, createSignal, createEffect, on, onMount, onCleanup } from "solid-js";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import { TbOutlineRefresh, TbOutlinePhoto, TbOutlineCheck } from "solid-icons/tb";
+import { getPlayerProfile, renderMatchCard } from "@src/ipc/commands";
 import { rankName, rankIcon, mapName } from "@src/utils/ranks";
 import type { ConnectionStatus, PlayerProfile } from "@src/types/valorant";
 
@@ -12,6 +13,7 @@ const Dashboard: Component<DashboardProps> = (props) => {
   const [profile, setProfile] = createSignal<PlayerProfile | null>(null);
   const [loading, setLoading] = createSignal(false);
   const [refreshing, setRefreshing] = createSignal(false);
+  const [cardStatus, setCardStatus] = createSignal<Record<string, "rendering" | "copied" | "error">>({});
 
   const fetchProfile = (isRefresh = false) => {
     if (isRefresh) setRefreshing(true);
@@ -27,6 +29,28 @@ const Dashboard: Component<DashboardProps> = (props) => {
     else setProfile(null);
   }));
 
+  let unlistenPostMatch: UnlistenFn | null = null;
+
+  onMount(async () => {
+    unlistenPostMatch = await listen("post-match", () => {
+      if (props.status === "connected") fetchProfile(true);
+    });
+  });
+
+  onCleanup(() => unlistenPostMatch?.());
+
+  const shareCard = async (matchId: string) => {
+    if (cardStatus()[matchId] === "rendering") return;
+    setCardStatus(prev => ({ ...prev, [matchId]: "rendering" }));
+    try {
+      await renderMatchCard(matchId, true);
+      setCardStatus(prev => ({ ...prev, [matchId]: "copied" }));
+    } catch {
+      setCardStatus(prev => ({ ...prev, [matchId]: "error" }));
+    }
+    setTimeout(() => setCardStatus(prev => { const n = { ...prev }; delete n[matchId]; return n; }), 2000);
+  };
+
   const avatarUrl = () => {
     const id = profile()?.info.playerCardId;
     return id ? `https://media.valorant-api.com/playercards/${id}/largeart.png` : null;
@@ -159,6 +183,15 @@ const Dashboard: Component<DashboardProps> = (props) => {
                           <div class={`dash-comp-rr ${update.rrChange > 0 ? "rr-pos" : update.rrChange < 0 ? "rr-neg" : ""}`}>
                             {update.rrChange > 0 ? "+" : ""}{update.rrChange}
                           </div>
+                          <button
+                            class={`dash-comp-share ${cardStatus()[update.matchId] === "rendering" ? "dash-comp-share-busy" : ""} ${cardStatus()[update.matchId] === "error" ? "dash-comp-share-error" : ""}`}
+                            onClick={() => shareCard(update.matchId)}
+                            title="Copy match card"
+                          >
+                            <Show when={cardStatus()[update.matchId] === "copied"} fallback={<TbOutlinePhoto size={12} />}>
+                              <TbOutlineCheck size={12} />
+                            </Show>
+                          </button>
                         </div>
                       )}
                     </For>
@@ -1,17 +1,22 @@
-import { Component, Show, For, createSignal, createEffect, on, onCleanup } from "solid-js";
-import { getAgents, getPregameState, instalockAgent } from "@src/ipc/commands";
-import type { ConnectionStatus, AgentInfo, PregameState } from "@src/types/valorant";
-import type { InstalockTiming } from "./Settings";
+import { Component, Show, For, createSignal, createEffect, on, onMount, onCleanup } from "solid-js";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import { getAgents, getPregameState, getAutomationStatus } from "@src/ipc/commands";
+import type { QueueProfile, HoverMode } from "@src/ipc/commands";
+import type { ConnectionStatus, AgentInfo, PregameState, AutomationStatus } from "@src/types/valorant";
+import { QUEUES, queueLabel, queueProfile, withQueueProfile } from "@src/utils/queues";
 
 interface InstaLockProps {
   status: ConnectionStatus;
-  instalockTiming: InstalockTiming;
   active: boolean;
   onActiveChange: (v: boolean) => void;
-  selectedAgent: string | null;
-  onAgentChange: (v: string | null) => void;
-  mapOverrides: Record<string, string>;
-  onOverridesChange: (v: Record<string, string>) => void;
+  agentPriority: string[];
+  onPriorityChange: (v: string[]) => void;
+  mapPriority: Record<string, string[]>;
+  onMapPriorityChange: (v: Record<string, string[]>) => void;
+  hoverMode: HoverMode;
+  onHoverModeChange: (v: HoverMode) => void;
+  queues: Record<string, QueueProfile>;
+  onQueuesChange: (v: Record<string, QueueProfile>) => void;
   configLoaded: boolean;
 }
 
@@ -55,16 +60,59 @@ const preloadMapImages = async () => {
 
 const cachedMapSplash = (uuid: string) => mapImageCache.get(uuid) || mapSplash(uuid);
 
+const togglePriority = (list: string[], uuid: string) =>
+  list.includes(uuid) ? list.filter(x => x !== uuid) : [...list, uuid];
+
+const PriorityBar: Component<{ list: string[]; agents: AgentInfo[]; onChange: (v: string[]) => void }> = (props) => {
+  const moveUp = (index: number) => {
+    if (index === 0) return;
+    const next = [...props.list];
+    [next[index - 1], next[index]] = [next[index], next[index - 1]];
+    props.onChange(next);
+  };
+
+  return (
+    <div class="il-priority">
+      <span class="il-priority-label">Priority</span>
+      <Show when={props.list.length > 0} fallback={<span class="il-priority-empty">Click agents to add them in order</span>}>
+        <For each={props.list}>
+          {(uuid, i) => {
+            const agent = () => props.agents.find(a => a.uuid === uuid);
+            return (
+              <div class="il-priority-chip" title={i() > 0 ? "Click to move up" : undefined} onClick={() => moveUp(i())}>
+                <span class="il-priority-index">{i() + 1}</span>
+                <Show when={agent()}>
+                  <img src={agent()!.icon} class="il-priority-icon" alt="" />
+                </Show>
+                <span class="il-priority-name">{agent()?.name ?? "Unknown"}</span>
+                <button
+                  class="il-priority-remove"
+                  onClick={(e) => { e.stopPropagation(); props.onChange(props.list.filter(x => x !== uuid)); }}
+                >
+                  ×
+                </button>
+              </div>
+            );
+          }}
+        </For>
+      </Show>
+    </div>
+  );
+};
+
 const InstaLock: Component<InstaLockProps> = (props) => {
   const [agents, setAgents] = createSignal<AgentInfo[]>([]);
   const [loading, setLoading] = createSignal(false);
   const [filterRole, setFilterRole] = createSignal<string | null>(null);
-  const [tab, setTab] = createSignal<"all" | "permap">("all");
+  const [tab, setTab] = createSignal<"all" | "permap" | "perqueue">("all");
   const [editingMap, setEditingMap] = createSignal<string | null>(null);
+  const [editingQueue, setEditingQueue] = createSignal<string | null>(null);
   const [pregame, setPregame] = createSignal<PregameState | null>(null);
-  const [lockStatus, setLockStatus] = createSignal<"idle" | "locking" | "locked">("idle");
+  const [automation, setAutomation] = createSignal<AutomationStatus | null>(null);
   let pollInterval: ReturnType<typeof setInterval> | null = null;
-  let hasLockedThisMatch = "";
+  let unlistenStatus: UnlistenFn | null = null;
+
+  const lockStatus = () => automation()?.instalock.state ?? "idle";
 
   let agentPollInterval: ReturnType<typeof setInterval> | null = null;
 
@@ -87,61 +135,35 @@ const InstaLock: Component<InstaLockProps> = (props) => {
     }
   }));
 
-  const getAgentForMap = (mapName: string): string | null => {
-    const overrides = props.mapOverrides;
-    if (overrides[mapName]) return overrides[mapName];
-    return props.selectedAgent;
+  const priorityFor = (queueId: string, mapName: string): string[] => {
+    const queue = queueProfile(props.queues, queueId);
+    const useQueue = queue.agentPriority.length > 0 || Object.keys(queue.mapPriority).length > 0;
+    const perMap = useQueue ? queue.mapPriority : props.mapPriority;
+    const global = useQueue ? queue.agentPriority : props.agentPriority;
+    const list = [...(perMap[mapName] ?? [])];
+    for (const uuid of global) {
+      if (!list.includes(uuid)) list.push(uuid);
+    }
+    return list;
   };
 
   const pollPregame = async () => {
     if (props.status !== "connected" || !props.active) {
       setPregame(null);
-      setLockStatus("idle");
       return;
     }
-
     try {
-      const state = await getPregameState();
-      setPregame(state);
-
-      if (!state) {
-        if (lockStatus() === "locked") setLockStatus("idle");
-        hasLockedThisMatch = "";
-        return;
-      }
-
-      if (state.locked) {
-        setLockStatus("locked");
-        return;
-      }
-
-      if (hasLockedThisMatch === state.matchId) return;
-
-      const agentId = getAgentForMap(state.mapName);
-      if (!agentId) return;
-
-      setLockStatus("locking");
-      const timing = props.instalockTiming;
-      const selDelay = timing.preset === "humanized" ? 400 + Math.random() * 800 : timing.selectDelay;
-      const lckDelay = timing.preset === "humanized" ? 200 + Math.random() * 600 : timing.lockDelay;
-      try {
-        if (selDelay > 0) {
-          await new Promise(r => setTimeout(r, selDelay));
-        }
-        await instalockAgent(state.matchId, agentId);
-        if (lckDelay > 0) {
-          await new Promise(r => setTimeout(r, lckDelay));
-        }
-        hasLockedThisMatch = state.matchId;
-        setLockStatus("locked");
-      } catch {
-        setLockStatus("idle");
-      }
+      setPregame(await getPregameState());
     } catch {
       setPregame(null);
     }
   };
 
+  onMount(async () => {
+    getAutomationStatus().then(setAutomation).catch(() => {});
+    unlistenStatus = await listen<AutomationStatus>("automation-status", (event) => setAutomation(event.payload));
+  });
+
   createEffect(on([() => props.active, () => props.status], () => {
     if (pollInterval) clearInterval(pollInterval);
     if (props.active && props.status === "connected") {
@@ -149,13 +171,13 @@ const InstaLock: Component<InstaLockProps> = (props) => {
       pollInterval = setInterval(pollPregame, 1500);
     } else {
       setPregame(null);
-      setLockStatus("idle");
     }
   }));
 
   onCleanup(() => {
     if (pollInterval) clearInterval(pollInterval);
     if (agentPollInterval) clearInterval(agentPollInterval);
+    unlistenStatus?.();
   });
 
   const roles = () => {
@@ -175,51 +197,59 @@ const InstaLock: Component<InstaLockProps> = (props) => {
     });
   };
 
-  const selectedAgent = () => agents().find(a => a.uuid === props.selectedAgent);
   const agentByUuid = (uuid: string) => agents().find(a => a.uuid === uuid);
+  const agentNames = (list: string[]) => list.map(uuid => agentByUuid(uuid)?.name).filter(Boolean).join(" → ");
 
   createEffect(on(() => agents(), (list) => {
     if (!props.configLoaded || list.length === 0) return;
-    if (props.selectedAgent && !list.find(a => a.uuid === props.selectedAgent && a.unlocked)) {
-      props.onAgentChange(null);
-    }
-    const overrides = { ...props.mapOverrides };
+    const owned = (uuid: string) => !!list.find(a => a.uuid === uuid && a.unlocked);
+    const priority = props.agentPriority.filter(owned);
+    if (priority.length !== props.agentPriority.length) props.onPriorityChange(priority);
+    const perMap: Record<string, string[]> = {};
     let changed = false;
-    for (const [map, agentId] of Object.entries(overrides)) {
-      if (!list.find(a => a.uuid === agentId && a.unlocked)) {
-        delete overrides[map];
-        changed = true;
-      }
+    for (const [map, agentIds] of Object.entries(props.mapPriority)) {
+      const kept = agentIds.filter(owned);
+      if (kept.length !== agentIds.length) changed = true;
+      if (kept.length > 0) perMap[map] = kept;
     }
-    if (changed) props.onOverridesChange(overrides);
+    if (changed) props.onMapPriorityChange(perMap);
   }));
 
+  const setMapList = (map: string, list: string[]) => {
+    const next = { ...props.mapPriority };
+    if (list.length > 0) next[map] = list;
+    else delete next[map];
+    props.onMapPriorityChange(next);
+  };
+
+  const setQueue = (queueId: string, patch: Partial<QueueProfile>) => {
+    props.onQueuesChange(withQueueProfile(props.queues, queueId, patch));
+  };
+
   const handleSelect = (agent: AgentInfo) => {
     if (!agent.unlocked) return;
+    const queue = editingQueue();
+    if (queue) {
+      setQueue(queue, { agentPriority: togglePriority(queueProfile(props.queues, queue).agentPriority, agent.uuid) });
+      return;
+    }
     const editing = editingMap();
     if (editing) {
-      props.onOverridesChange({ ...props.mapOverrides, [editing]: agent.uuid });
-      setEditingMap(null);
+      setMapList(editing, togglePriority(props.mapPriority[editing] ?? [], agent.uuid));
     } else {
-      props.onAgentChange(props.selectedAgent === agent.uuid ? null : agent.uuid);
+      props.onPriorityChange(togglePriority(props.agentPriority, agent.uuid));
     }
   };
 
-  const clearMapOverride = (map: string) => {
-    const next = { ...props.mapOverrides };
-    delete next[map];
-    props.onOverridesChange(next);
-  };
-
-  const hasAnyAgent = () => !!props.selectedAgent || Object.keys(props.mapOverrides).length > 0;
+  const hasAnyAgent = () => props.agentPriority.length > 0 || Object.keys(props.mapPriority).length > 0;
 
   const getMapAgent = (map: string) => {
-    const perMap = props.mapOverrides[map];
-    const def = selectedAgent();
-    if (perMap) {
-      return { agent: agentByUuid(perMap), isOverride: !!def, isPerMap: true };
+    const perMap = props.mapPriority[map] ?? [];
+    if (perMap.length > 0) {
+      return { agent: agentByUuid(perMap[0]), extra: perMap.length - 1, isPerMap: true };
     }
-    return { agent: def, isOverride: false, isPerMap: false };
+    const list = props.agentPriority;
+    return { agent: list.length > 0 ? agentByUuid(list[0]) : undefined, extra: Math.max(list.length - 1, 0), isPerMap: false };
   };
 
   return (
@@ -255,13 +285,53 @@ const InstaLock: Component<InstaLockProps> = (props) => {
           </div>
         </div>
 
+        <div class="il-mode">
+          <div class="st-export-formats">
+            <button
+              class={`st-export-format ${!props.hoverMode.hoverOnly ? "st-export-format-active" : ""}`}
+              onClick={() => props.onHoverModeChange({ ...props.hoverMode, hoverOnly: false })}
+            >
+              Lock
+            </button>
+            <button
+              class={`st-export-format ${props.hoverMode.hoverOnly ? "st-export-format-active" : ""}`}
+              onClick={() => props.onHoverModeChange({ ...props.hoverMode, hoverOnly: true })}
+            >
+              Hover Only
+            </button>
+          </div>
+          <Show when={props.hoverMode.hoverOnly}>
+            <label class="il-mode-autolock">
+              <input
+                type="checkbox"
+                checked={props.hoverMode.autoLock}
+                onChange={(e) => props.onHoverModeChange({ ...props.hoverMode, autoLock: e.currentTarget.checked })}
+              />
+              Auto-lock if still hovered with
+              <input
+                type="number"
+                class="il-mode-secs"
+                min={1}
+                max={60}
+                value={props.hoverMode.autoLockSecs}
+                disabled={!props.hoverMode.autoLock}
+                onChange={(e) => props.onHoverModeChange({ ...props.hoverMode, autoLockSecs: Math.min(60, Math.max(1, parseInt(e.currentTarget.value) || 5)) })}
+              />
+              s left
+            </label>
+          </Show>
+        </div>
+
         <div class="il-tabs">
-          <button class={`il-tab ${tab() === "all" ? "il-tab-active" : ""}`} onClick={() => { setTab("all"); setEditingMap(null); }}>
+          <button class={`il-tab ${tab() === "all" ? "il-tab-active" : ""}`} onClick={() => { setTab("all"); setEditingMap(null); setEditingQueue(null); }}>
             All Maps
           </button>
           <button class={`il-tab ${tab() === "permap" ? "il-tab-active" : ""}`} onClick={() => { setTab("permap"); setEditingMap(null); }}>
             Per Map
           </button>
+          <button class={`il-tab ${tab() === "perqueue" ? "il-tab-active" : ""}`} onClick={() => { setTab("perqueue"); setEditingQueue(null); }}>
+            Per Queue
+          </button>
         </div>
 
         <Show when={tab() === "all"}>
@@ -284,11 +354,13 @@ const InstaLock: Component<InstaLockProps> = (props) => {
             </For>
           </div>
 
+          <PriorityBar list={props.agentPriority} agents={agents()} onChange={props.onPriorityChange} />
+
           <div class="il-grid il-fade-in">
             <For each={filtered()}>
               {(agent) => (
                 <button
-                  class={`il-agent ${!agent.unlocked ? "il-agent-locked" : ""} ${props.selectedAgent === agent.uuid ? "il-agent-selected" : ""}`}
+                  class={`il-agent ${!agent.unlocked ? "il-agent-locked" : ""} ${props.agentPriority.includes(agent.uuid) ? "il-agent-selected" : ""}`}
                   onClick={() => handleSelect(agent)}
                   disabled={!agent.unlocked}
                 >
@@ -297,6 +369,9 @@ const InstaLock: Component<InstaLockProps> = (props) => {
                     <Show when={!agent.unlocked}>
                       <div class="il-agent-lock-overlay" />
                     </Show>
+                    <Show when={props.agentPriority.includes(agent.uuid)}>
+                      <span class="il-agent-order">{props.agentPriority.indexOf(agent.uuid) + 1}</span>
+                    </Show>
                   </div>
                   <span class="il-agent-name">{agent.name}</span>
                 </button>
@@ -310,18 +385,22 @@ const InstaLock: Component<InstaLockProps> = (props) => {
             {(map) => (
               <div class="il-fade-in">
                 <div class="il-permap-header">
-                  <p class="il-permap-hint">Select agent for <strong>{map()}</strong></p>
+                  <p class="il-permap-hint">Agent priority for <strong>{map()}</strong> — falls back to your global list</p>
                   <button class="il-filter-btn" onClick={() => setEditingMap(null)}>Back</button>
                 </div>
+                <PriorityBar list={props.mapPriority[map()] ?? []} agents={agents()} onChange={(v) => setMapList(map(), v)} />
                 <div class="il-grid" style={{ "margin-top": "10px" }}>
                   <For each={agents().filter(a => a.unlocked).sort((a, b) => a.name.localeCompare(b.name))}>
                     {(agent) => (
                       <button
-                        class={`il-agent ${props.mapOverrides[map()] === agent.uuid ? "il-agent-selected" : ""}`}
+                        class={`il-agent ${(props.mapPriority[map()] ?? []).includes(agent.uuid) ? "il-agent-selected" : ""}`}
                         onClick={() => handleSelect(agent)}
                       >
                         <div class="il-agent-img-wrap">
                           <img src={agent.icon} class="il-agent-img" alt="" />
+                          <Show when={(props.mapPriority[map()] ?? []).includes(agent.uuid)}>
+                            <span class="il-agent-order">{(props.mapPriority[map()] ?? []).indexOf(agent.uuid) + 1}</span>
+                          </Show>
                         </div>
                         <span class="il-agent-name">{agent.name}</span>
                       </button>
@@ -335,9 +414,9 @@ const InstaLock: Component<InstaLockProps> = (props) => {
           <Show when={!editingMap()}>
             <div class="il-fade-in">
               <div class="il-permap-header">
-                <p class="il-permap-hint">Click a map to override its agent</p>
-                <Show when={selectedAgent()}>
-                  <span class="il-permap-default">Default: {selectedAgent()!.name}</span>
+                <p class="il-permap-hint">Click a map to set its own agent priority</p>
+                <Show when={props.agentPriority.length > 0}>
+                  <span class="il-permap-default">Default: {agentNames(props.agentPriority)}</span>
                 </Show>
               </div>
               <div class="il-map-grid" style={{ "margin-top": "10px" }}>
@@ -354,14 +433,15 @@ const InstaLock: Component<InstaLockProps> = (props) => {
                           <div class="il-map-name">{m.name}</div>
                           <div class={`il-map-agent-name ${info().isPerMap ? "il-map-override" : ""}`}>
                             {info().agent ? info().agent!.name : "No agent set"}
-                            {info().isPerMap && info().isOverride ? " (override)" : info().isPerMap ? "" : info().agent ? " (default)" : ""}
+                            {info().extra > 0 ? ` +${info().extra}` : ""}
+                            {!info().isPerMap && info().agent ? " (default)" : ""}
                           </div>
                         </div>
                         <Show when={info().agent}>
                           <img src={info().agent!.icon} class="il-map-agent-icon" alt="" />
                         </Show>
                         <Show when={info().isPerMap}>
-                          <button class="il-map-clear" onClick={(e) => { e.stopPropagation(); clearMapOverride(m.name); }}>Reset</button>
+                          <button class="il-map-clear" onClick={(e) => { e.stopPropagation(); setMapList(m.name, []); }}>Reset</button>
                         </Show>
                       </div>
                     );
@@ -372,11 +452,107 @@ const InstaLock: Component<InstaLockProps> = (props) => {
           </Show>
         </Show>
 
+        <Show when={tab() === "perqueue"}>
+          <Show when={editingQueue()}>
+            {(queue) => {
+              const list = () => queueProfile(props.queues, queue()).agentPriority;
+              return (
+                <div class="il-fade-in">
+                  <div class="il-permap-header">
+                    <p class="il-permap-hint">Agent priority for <strong>{queueLabel(queue())}</strong> — leave empty to use your global list</p>
+                    <button class="il-filter-btn" onClick={() => setEditingQueue(null)}>Back</button>
+                  </div>
+                  <PriorityBar list={list()} agents={agents()} onChange={(v) => setQueue(queue(), { agentPriority: v })} />
+                  <div class="il-grid" style={{ "margin-top": "10px" }}>
+                    <For each={agents().filter(a => a.unlocked).sort((a, b) => a.name.localeCompare(b.name))}>
+                      {(agent) => (
+                        <button
+                          class={`il-agent ${list().includes(agent.uuid) ? "il-agent-selected" : ""}`}
+                          onClick={() => handleSelect(agent)}
+                        >
+                          <div class="il-agent-img-wrap">
+                            <img src={agent.icon} class="il-agent-img" alt="" />
+                            <Show when={list().includes(agent.uuid)}>
+                              <span class="il-agent-order">{list().indexOf(agent.uuid) + 1}</span>
+                            </Show>
+                          </div>
+                          <span class="il-agent-name">{agent.name}</span>
+                        </button>
+                      )}
+                    </For>
+                  </div>
+                </div>
+              );
+            }}
+          </Show>
+
+          <Show when={!editingQueue()}>
+            <div class="il-fade-in">
+              <div class="il-permap-header">
+                <p class="il-permap-hint">Turn instalock off or pick different agents for each queue</p>
+              </div>
+              <div class="il-queue-list">
+                <For each={QUEUES}>
+                  {(q) => {
+                    const profile = () => queueProfile(props.queues, q.id);
+                    return (
+                      <div class={`il-queue-row ${!profile().instalock ? "il-queue-off" : ""}`}>
+                        <span class="il-queue-name">{q.label}</span>
+                        <span class="il-queue-agents">
+                          {!profile().instalock ? "Instalock off" :
+                            profile().agentPriority.length > 0 ? agentNames(profile().agentPriority) : "Global priority"}
+                        </span>
+                        <button class="il-filter-btn" disabled={!profile().instalock} onClick={() => setEditingQueue(q.id)}>
+                          Agents
+                        </button>
+                        <button
+                          class={`il-toggle ${profile().instalock ? "il-toggle-on" : ""}`}
+                          onClick={() => setQueue(q.id, { instalock: !profile().instalock })}
+                        >
+                          <div class="il-toggle-knob" />
+                        </button>
+                      </div>
+                    );
+                  }}
+                </For>
+              </div>
+            </div>
+          </Show>
+        </Show>
+
+        <Show when={props.active && pregame() && pregame()!.allies.length > 0}>
+          <div class="il-lobby">
+            <div class="il-lobby-header">
+              <span class="il-lobby-title">Your Team</span>
+              <Show when={pregame()!.phaseTimeRemainingMs > 0}>
+                <span class="il-lobby-timer">{Math.ceil(pregame()!.phaseTimeRemainingMs / 1000)}s left</span>
+              </Show>
+            </div>
+            <div class="il-lobby-players">
+              <For each={pregame()!.allies}>
+                {(ally) => {
+                  const agent = () => ally.characterId ? agents().find(a => a.uuid === ally.characterId) : null;
+                  return (
+                    <div
+                      class={`il-lobby-player il-lobby-${ally.selectionState || "none"} ${ally.isSelf ? "il-lobby-self" : ""}`}
+                      title={`${agent()?.name ?? "No agent"} — ${ally.selectionState === "locked" ? "Locked" : ally.selectionState === "selected" ? "Hovering" : "Picking"}`}
+                    >
+                      <Show when={agent()} fallback={<div class="il-lobby-placeholder" />}>
+                        <img src={agent()!.icon} class="il-lobby-icon" alt="" />
+                      </Show>
+                    </div>
+                  );
+                }}
+              </For>
+            </div>
+          </div>
+        </Show>
+
         <div class="il-status-banner">
           <div class={`il-status-dot ${
             !props.active ? "il-status-dot-inactive" :
             lockStatus() === "locked" ? "il-status-dot-locked" :
-            lockStatus() === "locking" ? "il-status-dot-locking" :
+            lockStatus() === "locking" || lockStatus() === "hovering" ? "il-status-dot-locking" :
             pregame() ? "il-status-dot-locking" :
             "il-status-dot-waiting"
           }`} />
@@ -384,26 +560,39 @@ const InstaLock: Component<InstaLockProps> = (props) => {
             <Show when={!props.active}>Instalock is inactive</Show>
             <Show when={props.active && !hasAnyAgent() && !pregame()}>No agent selected</Show>
             <Show when={props.active && lockStatus() === "locked"}>
-              Agent locked{pregame()?.mapName ? ` on ${pregame()!.mapName}` : ""}
+              {automation()?.dryRun ? "Would have locked" : "Agent locked"}{automation()?.instalock.mapName ? ` on ${automation()!.instalock.mapName}` : ""}
               {(() => {
-                const a = pregame()?.lockedAgent ? agents().find(x => x.uuid === pregame()!.lockedAgent) : null;
+                const id = automation()?.instalock.agentId;
+                const a = id ? agentByUuid(id) : null;
                 return a ? <> as <span class="il-status-agent">{a.name}</span></> : null;
               })()}
             </Show>
+            <Show when={props.active && lockStatus() === "hovering"}>
+              Hovering <span class="il-status-agent">{agentByUuid(automation()?.instalock.agentId ?? "")?.name ?? "agent"}</span>
+              {automation()?.instalock.message ? ` — ${automation()!.instalock.message}` : ""}
+            </Show>
             <Show when={props.active && lockStatus() === "locking"}>
-              Locking agent{pregame()?.mapName ? ` on ${pregame()!.mapName}` : ""}...
+              Locking {agentByUuid(automation()?.instalock.agentId ?? "")?.name ?? "agent"}
+              {automation()?.instalock.mapName ? ` on ${automation()!.instalock.mapName}` : ""}
+              {automation()?.instalock.message ? ` — ${automation()!.instalock.message}` : "..."}
             </Show>
-            <Show when={props.active && hasAnyAgent() && lockStatus() === "idle" && !pregame()}>
-              Waiting for match{props.selectedAgent ? <> — will lock <span class="il-status-agent">{selectedAgent()?.name}</span></> : <> — per-map agents configured</>}
+            <Show when={props.active && (lockStatus() === "failed" || lockStatus() === "disabled")}>
+              {automation()!.instalock.message}
             </Show>
-            <Show when={props.active && hasAnyAgent() && lockStatus() === "idle" && pregame() && !pregame()!.locked}>
+            <Show when={props.active && hasAnyAgent() && (lockStatus() === "idle" || lockStatus() === "waiting") && !pregame()}>
+              Waiting for match{props.agentPriority.length > 0 ? <> — priority <span class="il-status-agent">{agentNames(props.agentPriority)}</span></> : <> — per-map agents configured</>}
+            </Show>
+            <Show when={props.active && hasAnyAgent() && (lockStatus() === "idle" || lockStatus() === "waiting") && pregame() && !pregame()!.locked}>
               In agent select on <span class="il-status-agent">{pregame()!.mapName}</span>
               {(() => {
-                const a = getAgentForMap(pregame()!.mapName);
-                return a ? <> — will lock {agents().find(x => x.uuid === a)?.name}</> : <> — no agent for this map</>;
+                const list = priorityFor(pregame()!.queueId, pregame()!.mapName);
+                return list.length > 0 ? <> — will try {agentNames(list)}</> : <> — no agent for this map</>;
               })()}
             </Show>
           </span>
+          <Show when={automation()?.dryRun}>
+            <span class="il-dry-run">Dry run</span>
+          </Show>
         </div>
       </Show>
     </div>
@@ -1,16 +1,65 @@
 import { Component, Show, For, createSignal, createEffect, on, onCleanup } from "solid-js";
-import { getLiveMatch } from "@src/ipc/commands";
+import { getLiveMatch, getMatchLoadouts, getWatchlist } from "@src/ipc/commands";
 import { rankName, rankIcon } from "@src/utils/ranks";
-import type { ConnectionStatus, LiveMatch as LiveMatchType, LiveMatchPlayer } from "@src/types/valorant";
+import { useMatchPhase } from "@src/hooks/useMatchPhase";
+import WatchlistEditor, { WatchTags } from "../components/WatchlistEditor";
+import type { ConnectionStatus, LiveMatch as LiveMatchType, LiveMatchPlayer, MatchLoadouts, PlayerLoadout, TeamAggregate, WatchEntry } from "@src/types/valorant";
 
 interface LiveMatchProps {
   status: ConnectionStatus;
 }
 
-const PlayerRow: Component<{ player: LiveMatchPlayer }> = (props) => {
+const PARTY_COLORS = ["#6b8aed", "#d0a040", "#40c0a0", "#c060d0", "#e07040"];
+
+const partyColor = (group: number) => PARTY_COLORS[(group - 1) % PARTY_COLORS.length];
+
+const TeamStats: Component<{ stats: TeamAggregate | null | undefined }> = (props) => (
+  <Show when={props.stats && props.stats.rankedPlayers > 0}>
+    <span class="lm-team-stats">
+      <span title="Average rank">Avg {rankName(Math.round(props.stats!.avgRank))}</span>
+      <span title="Median rank">Med {rankName(Math.round(props.stats!.medianRank))}</span>
+      <Show when={props.stats!.avgPeakRank > 0}>
+        <span title="Average peak rank">Peak {rankName(Math.round(props.stats!.avgPeakRank))}</span>
+      </Show>
+      <Show when={props.stats!.maxLevel > 0}>
+        <span title={`Level spread ${props.stats!.levelSpread}`}>Lv {props.stats!.minLevel}–{props.stats!.maxLevel}</span>
+      </Show>
+    </span>
+  </Show>
+);
+
+const LoadoutStrip: Component<{ loadout: PlayerLoadout }> = (props) => {
+  const skinned = () => props.loadout.weapons.filter(w => w.skin && w.skin.icon && !w.skin.name.startsWith("Standard"));
+  return (
+    <div class="lm-loadout">
+      <Show when={skinned().length > 0} fallback={<span class="lm-loadout-empty">Default skins</span>}>
+        <For each={skinned()}>
+          {(w) => (
+            <img
+              src={w.skin!.icon}
+              class="lm-loadout-skin"
+              alt=""
+              title={`${w.weaponName}: ${w.skin!.name}${w.buddy ? ` · ${w.buddy.name}` : ""}`}
+            />
+          )}
+        </For>
+      </Show>
+      <For each={props.loadout.sprays}>
+        {(spray) => <img src={spray.icon} class="lm-loadout-spray" alt="" title={spray.name} />}
+      </For>
+    </div>
+  );
+};
+
+const PlayerRow: Component<{ player: LiveMatchPlayer; watchEntry: WatchEntry | null; onWatchlistChange: () => void; loadout?: PlayerLoadout | null }> = (props) => {
   const p = () => props.player;
   return (
-    <div class={`lm-player ${p().isSelf ? "lm-player-self" : ""}`}>
+    <div class={`lm-player ${p().isSelf ? "lm-player-self" : ""} ${p().watchTags.length > 0 ? "lm-player-watched" : ""}`}>
+      <div
+        class={`lm-party-bracket ${p().partyGroup ? "lm-party-bracket-active" : ""}`}
+        style={p().partyGroup ? { "background": partyColor(p().partyGroup!) } : {}}
+        title={p().partyGroup ? `Party ${p().partyGroup}` : undefined}
+      />
       <div class="lm-player-agent">
         <Show when={p().agentIcon} fallback={<div class="lm-agent-placeholder" />}>
           <img src={p().agentIcon} class="lm-agent-icon" alt="" />
@@ -26,13 +75,57 @@ const PlayerRow: Component<{ player: LiveMatchPlayer }> = (props) => {
               <span class="lm-player-tag">#{p().tagLine}</span>
             </Show>
           </Show>
+          <WatchTags tags={p().watchTags} />
+          <Show when={!p().isSelf}>
+            <WatchlistEditor
+              puuid={p().puuid}
+              gameName={p().gameName}
+              tagLine={p().tagLine}
+              entry={props.watchEntry}
+              onChange={props.onWatchlistChange}
+            />
+          </Show>
         </span>
         <div class="lm-player-sub">
           <span class="lm-player-agent-name">{p().agentName}</span>
           <Show when={p().accountLevel > 0}>
             <span class="lm-player-level">Lv. {p().accountLevel}</span>
           </Show>
+          <Show when={p().encounters}>
+            {(e) => (
+              <span
+                class="lm-player-encounter"
+                title={`Teammate ${e().asTeammate}× · Enemy ${e().asEnemy}× · Last seen ${new Date(e().lastSeen).toLocaleDateString()}`}
+              >
+                Seen {e().timesSeen}×
+                <Show when={e().wins + e().losses + e().draws > 0}>
+                  {" "}· {e().wins}W {e().losses}L
+                </Show>
+              </span>
+            )}
+          </Show>
+          <Show when={p().recentForm}>
+            {(f) => (
+              <span
+                class="lm-player-form"
+                title={`Last ${f().matches}: ${f().wins}W ${f().losses}L${f().topAgents.length > 0 ? ` · ${f().topAgents.join(", ")}` : ""}`}
+              >
+                <span class={f().winRate >= 50 ? "lm-form-good" : "lm-form-bad"}>{Math.round(f().winRate)}%</span>
+                {" "}· {f().kd.toFixed(2)} KD · {Math.round(f().hsPercent)}% HS
+                <Show when={f().rrTrend.length > 0}>
+                  <span class="lm-form-trend">
+                    <For each={f().rrTrend}>
+                      {(rr) => <span class={rr >= 0 ? "lm-form-good" : "lm-form-bad"}>{rr > 0 ? `+${rr}` : rr}</span>}
+                    </For>
+                  </span>
+                </Show>
+              </span>
+            )}
+          </Show>
         </div>
+        <Show when={props.loadout}>
+          {(l) => <LoadoutStrip loadout={l()} />}
+        </Show>
       </div>
       <div class="lm-player-rank">
         <Show when={p().rank > 0} fallback={<span class="lm-rank-unranked">Unranked</span>}>
@@ -63,6 +156,10 @@ const LiveMatch: Component<LiveMatchProps> = (props) => {
   const [match, setMatch] = createSignal<LiveMatchType | null>(null);
   const [loading, setLoading] = createSignal(false);
   const [cachedMatchId, setCachedMatchId] = createSignal("");
+  const [watchlist, setWatchlist] = createSignal<WatchEntry[]>([]);
+  const [showLoadouts, setShowLoadouts] = createSignal(false);
+  const [loadouts, setLoadouts] = createSignal<MatchLoadouts | null>(null);
+  const [loadingLoadouts, setLoadingLoadouts] = createSignal(false);
   let pollInterval: ReturnType<typeof setInterval> | null = null;
 
   const fetchMatch = async () => {
@@ -87,13 +184,14 @@ const LiveMatch: Component<LiveMatchProps> = (props) => {
           return {
             ...prev,
             phase: m.phase,
+            teamSummary: m.teamSummary,
             allyTeam: prev.allyTeam.map(p => {
               const updated = m.allyTeam.find(u => u.puuid === p.puuid);
-              return updated ? { ...p, agentId: updated.agentId, agentName: updated.agentName, agentIcon: updated.agentIcon } : p;
+              return updated ? { ...p, agentId: updated.agentId, agentName: updated.agentName, agentIcon: updated.agentIcon, watchTags: updated.watchTags, partyGroup: updated.partyGroup, recentForm: updated.recentForm } : p;
             }),
             enemyTeam: m.enemyTeam.length > 0 && prev.enemyTeam.length === 0 ? m.enemyTeam : prev.enemyTeam.map(p => {
               const updated = m.enemyTeam.find(u => u.puuid === p.puuid);
-              return updated ? { ...p, agentId: updated.agentId, agentName: updated.agentName, agentIcon: updated.agentIcon } : p;
+              return updated ? { ...p, agentId: updated.agentId, agentName: updated.agentName, agentIcon: updated.agentIcon, watchTags: updated.watchTags, partyGroup: updated.partyGroup, recentForm: updated.recentForm } : p;
             }),
           };
         });
@@ -103,8 +201,53 @@ const LiveMatch: Component<LiveMatchProps> = (props) => {
     }
   };
 
+  useMatchPhase((t) => {
+    if (props.status === "connected" && (t.to === "Pregame" || t.to === "InGame" || t.to === "Menus")) {
+      fetchMatch();
+    }
+  });
+
+  const fetchWatchlist = () => {
+    getWatchlist().then(setWatchlist).catch(() => {});
+  };
+
+  const onWatchlistChange = () => {
+    fetchWatchlist();
+    fetchMatch();
+  };
+
+  const watchEntry = (puuid: string) => watchlist().find(e => e.puuid === puuid) ?? null;
+
+  const fetchLoadouts = async () => {
+    setLoadingLoadouts(true);
+    try {
+      setLoadouts(await getMatchLoadouts());
+    } catch {}
+    setLoadingLoadouts(false);
+  };
+
+  const toggleLoadouts = () => {
+    const next = !showLoadouts();
+    setShowLoadouts(next);
+    if (next) fetchLoadouts();
+  };
+
+  const playerLoadout = (puuid: string) => {
+    if (!showLoadouts()) return null;
+    const l = loadouts();
+    if (!l || l.matchId !== match()?.matchId) return null;
+    return l.players.find(p => p.puuid === puuid) ?? null;
+  };
+
+  createEffect(on(() => [match()?.matchId, match()?.phase], () => {
+    if (showLoadouts() && match() && (loadouts()?.matchId !== match()!.matchId || loadouts()?.phase !== match()!.phase)) {
+      fetchLoadouts();
+    }
+  }));
+
   const refresh = async () => {
     setLoading(true);
+    fetchWatchlist();
     setCachedMatchId("");
     await fetchMatch();
     setLoading(false);
@@ -183,6 +326,26 @@ const LiveMatch: Component<LiveMatchProps> = (props) => {
                     </span>
                   </div>
                 </div>
+                <Show when={m().teamSummary?.winProbability != null}>
+                  <div
+                    class="lm-win-prob"
+                    title={m().teamSummary!.calibrationMatches >= 10
+                      ? `Calibrated on ${m().teamSummary!.calibrationMatches} past matches`
+                      : "Not enough local results to calibrate yet"}
+                  >
+                    <span class="lm-win-prob-label">Win Chance</span>
+                    <span class={`lm-win-prob-value ${m().teamSummary!.winProbability! >= 50 ? "lm-form-good" : "lm-form-bad"}`}>
+                      {Math.round(m().teamSummary!.winProbability!)}%
+                    </span>
+                  </div>
+                </Show>
+                <button
+                  class={`lm-loadout-toggle ${showLoadouts() ? "lm-loadout-toggle-active" : ""}`}
+                  onClick={toggleLoadouts}
+                  disabled={loadingLoadouts()}
+                >
+                  {loadingLoadouts() ? "Loading..." : showLoadouts() ? "Hide Skins" : "Show Skins"}
+                </button>
               </div>
 
               <div class="lm-teams">
@@ -200,7 +363,7 @@ const LiveMatch: Component<LiveMatchProps> = (props) => {
                         <span class="lm-col-peak">Peak</span>
                       </div>
                       <For each={m().allyTeam}>
-                        {(player) => <PlayerRow player={player} />}
+                        {(player) => <PlayerRow player={player} watchEntry={watchEntry(player.puuid)} onWatchlistChange={onWatchlistChange} loadout={playerLoadout(player.puuid)} />}
                       </For>
                     </div>
                   </div>
@@ -208,6 +371,7 @@ const LiveMatch: Component<LiveMatchProps> = (props) => {
                   <div class="lm-team">
                     <div class="lm-team-header lm-team-ally">
                       <span class="lm-team-label">Your Team</span>
+                      <TeamStats stats={m().teamSummary?.ally} />
                       <span class="lm-team-count">{m().allyTeam.length} players</span>
                     </div>
                     <div class="lm-team-list">
@@ -218,7 +382,7 @@ const LiveMatch: Component<LiveMatchProps> = (props) => {
                         <span class="lm-col-peak">Peak</span>
                       </div>
                       <For each={m().allyTeam}>
-                        {(player) => <PlayerRow player={player} />}
+                        {(player) => <PlayerRow player={player} watchEntry={watchEntry(player.puuid)} onWatchlistChange={onWatchlistChange} loadout={playerLoadout(player.puuid)} />}
                       </For>
                     </div>
                   </div>
@@ -227,6 +391,7 @@ const LiveMatch: Component<LiveMatchProps> = (props) => {
                     <div class="lm-team">
                       <div class="lm-team-header lm-team-enemy">
                         <span class="lm-team-label">Enemy Team</span>
+                        <TeamStats stats={m().teamSummary?.enemy} />
                         <span class="lm-team-count">{m().enemyTeam.length} players</span>
                       </div>
                       <div class="lm-team-list">
@@ -237,7 +402,7 @@ const LiveMatch: Component<LiveMatchProps> = (props) => {
                           <span class="lm-col-peak">Peak</span>
                         </div>
                         <For each={m().enemyTeam}>
-                          {(player) => <PlayerRow player={player} />}
+                          {(player) => <PlayerRow player={player} watchEntry={watchEntry(player.puuid)} onWatchlistChange={onWatchlistChange} loadout={playerLoadout(player.puuid)} />}
                         </For>
                       </div>
                     </div>
@@ -1,6 +1,10 @@
-import { Component, Show, For, createSignal, createEffect, on, onCleanup } from "solid-js";
-import { getPregameState, dodgeMatch } from "@src/ipc/commands";
-import type { ConnectionStatus, PregameState } from "@src/types/valorant";
+import { Component, Show, For, createSignal, createEffect, on, onMount, onCleanup } from "solid-js";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import { getAutomationStatus, getDodgePenalties } from "@src/ipc/commands";
+import type { QueueProfile, DodgeRule, DodgeCap } from "@src/ipc/commands";
+import type { ConnectionStatus, DodgeStatus, AutomationStatus, DodgeReport } from "@src/types/valorant";
+import { QUEUES, queueProfile, withQueueProfile } from "@src/utils/queues";
+import DodgeRules from "../components/DodgeRules";
 
 interface MapDodgeProps {
   status: ConnectionStatus;
@@ -8,6 +12,14 @@ interface MapDodgeProps {
   onActiveChange: (v: boolean) => void;
   blacklist: string[];
   onBlacklistChange: (v: string[]) => void;
+  queues: Record<string, QueueProfile>;
+  onQueuesChange: (v: Record<string, QueueProfile>) => void;
+  rules: DodgeRule[];
+  onRulesChange: (v: DodgeRule[]) => void;
+  testMode: boolean;
+  onTestModeChange: (v: boolean) => void;
+  dodgeCap: DodgeCap;
+  onDodgeCapChange: (v: DodgeCap) => void;
 }
 
 const COMP_MAPS: { name: string; uuid: string }[] = [
@@ -45,15 +57,45 @@ const cachedMapSplash = (uuid: string) => mapImageCache.get(uuid) || mapSplash(u
 
 const MapDodge: Component<MapDodgeProps> = (props) => {
   const blacklisted = () => new Set(props.blacklist);
-  const [pregame, setPregame] = createSignal<PregameState | null>(null);
-  const [dodgeStatus, setDodgeStatus] = createSignal<"idle" | "dodging" | "dodged">("idle");
-  let pollInterval: ReturnType<typeof setInterval> | null = null;
-  let hasDodgedThisMatch = "";
+  const [dodge, setDodge] = createSignal<DodgeStatus | null>(null);
+  const [tab, setTab] = createSignal<"maps" | "rules" | "penalties">("maps");
+  const [report, setReport] = createSignal<DodgeReport | null>(null);
+  const [dryRun, setDryRun] = createSignal(false);
+  let unlistenStatus: UnlistenFn | null = null;
+
+  const dodgeStatus = () => dodge()?.state ?? "idle";
+  const activeRules = () => props.rules.filter(r => r.enabled && r.conditions.length > 0).length;
+  const inPregame = () => !!dodge()?.matchId;
 
   createEffect(on(() => props.status, (status) => {
     if (status === "connected") preloadMapImages();
   }));
 
+  const refreshReport = () => {
+    if (props.status !== "connected") return;
+    getDodgePenalties().then(setReport).catch(() => {});
+  };
+
+  createEffect(on([tab, () => props.status], () => {
+    if (tab() === "penalties") refreshReport();
+  }));
+
+  onMount(async () => {
+    getAutomationStatus().then(s => { setDodge(s.dodge); setDryRun(s.dryRun); }).catch(() => {});
+    unlistenStatus = await listen<AutomationStatus>("automation-status", (event) => {
+      setDodge(event.payload.dodge);
+      setDryRun(event.payload.dryRun);
+      if (event.payload.dodge.state === "dodged") refreshReport();
+    });
+  });
+
+  const formatTime = (ms: number) =>
+    new Date(ms).toLocaleString(undefined, { month: "short", day: "numeric", hour: "2-digit", minute: "2-digit" });
+
+  onCleanup(() => {
+    unlistenStatus?.();
+  });
+
   const toggleMap = (name: string) => {
     const next = new Set(props.blacklist);
     if (next.has(name)) next.delete(name);
@@ -61,55 +103,11 @@ const MapDodge: Component<MapDodgeProps> = (props) => {
     props.onBlacklistChange([...next]);
   };
 
-  const pollForDodge = async () => {
-    if (props.status !== "connected" || !props.active) {
-      setPregame(null);
-      setDodgeStatus("idle");
-      return;
-    }
-
-    try {
-      const state = await getPregameState();
-      setPregame(state);
-
-      if (!state) {
-        if (dodgeStatus() === "dodged") setDodgeStatus("idle");
-        hasDodgedThisMatch = "";
-        return;
-      }
-
-      if (hasDodgedThisMatch === state.matchId) return;
-
-      if (blacklisted().has(state.mapName)) {
-        setDodgeStatus("dodging");
-        try {
-          await dodgeMatch(state.matchId);
-          hasDodgedThisMatch = state.matchId;
-          setDodgeStatus("dodged");
-        } catch {
-          setDodgeStatus("idle");
-        }
-      }
-    } catch {
-      setPregame(null);
-    }
+  const toggleQueue = (queueId: string) => {
+    const enabled = queueProfile(props.queues, queueId).dodge;
+    props.onQueuesChange(withQueueProfile(props.queues, queueId, { dodge: !enabled }));
   };
 
-  createEffect(on([() => props.active, () => props.status], () => {
-    if (pollInterval) clearInterval(pollInterval);
-    if (props.active && props.status === "connected") {
-      pollForDodge();
-      pollInterval = setInterval(pollForDodge, 1500);
-    } else {
-      setPregame(null);
-      setDodgeStatus("idle");
-    }
-  }));
-
-  onCleanup(() => {
-    if (pollInterval) clearInterval(pollInterval);
-  });
-
   return (
     <div class="mapdodge">
       <div class="md-header">
@@ -130,63 +128,190 @@ const MapDodge: Component<MapDodgeProps> = (props) => {
         </div>
       </div>
 
-      <div class="md-info">
-        <span class="md-blacklist-count">
-          <Show when={blacklisted().size > 0} fallback="No maps blacklisted">
-            <strong>{blacklisted().size}</strong> map{blacklisted().size !== 1 ? "s" : ""} blacklisted
-          </Show>
-        </span>
-        <span class="md-hint">Click a map to toggle blacklist</span>
+      <div class="il-tabs">
+        <button class={`il-tab ${tab() === "maps" ? "il-tab-active" : ""}`} onClick={() => setTab("maps")}>
+          Maps
+        </button>
+        <button class={`il-tab ${tab() === "rules" ? "il-tab-active" : ""}`} onClick={() => setTab("rules")}>
+          Rules{props.rules.length > 0 ? ` (${props.rules.length})` : ""}
+        </button>
+        <button class={`il-tab ${tab() === "penalties" ? "il-tab-active" : ""}`} onClick={() => setTab("penalties")}>
+          Penalties
+        </button>
       </div>
 
-      <div class="md-grid il-fade-in">
-        <For each={COMP_MAPS}>
-          {(m) => {
-            const isBlacklisted = () => blacklisted().has(m.name);
-            return (
+      <Show when={tab() === "maps"}>
+        <div class="md-info">
+          <span class="md-blacklist-count">
+            <Show when={blacklisted().size > 0} fallback="No maps blacklisted">
+              <strong>{blacklisted().size}</strong> map{blacklisted().size !== 1 ? "s" : ""} blacklisted
+            </Show>
+          </span>
+          <span class="md-hint">Click a map to toggle blacklist</span>
+        </div>
+
+        <div class="md-queues">
+          <span class="md-queues-label">Dodge in</span>
+          <For each={QUEUES}>
+            {(q) => (
               <button
-                class={`md-map ${isBlacklisted() ? "md-map-blacklisted" : ""}`}
-                onClick={() => toggleMap(m.name)}
+                class={`il-filter-btn ${queueProfile(props.queues, q.id).dodge ? "il-filter-active" : ""}`}
+                onClick={() => toggleQueue(q.id)}
               >
-                <div class="md-map-img-wrap">
-                  <img src={cachedMapSplash(m.uuid)} class="md-map-img" alt="" loading="lazy" />
-                  <Show when={isBlacklisted()}>
-                    <div class="md-map-overlay">
-                      <div class="md-map-x">✕</div>
+                {q.label}
+              </button>
+            )}
+          </For>
+        </div>
+
+        <div class="md-grid il-fade-in">
+          <For each={COMP_MAPS}>
+            {(m) => {
+              const isBlacklisted = () => blacklisted().has(m.name);
+              return (
+                <button
+                  class={`md-map ${isBlacklisted() ? "md-map-blacklisted" : ""}`}
+                  onClick={() => toggleMap(m.name)}
+                >
+                  <div class="md-map-img-wrap">
+                    <img src={cachedMapSplash(m.uuid)} class="md-map-img" alt="" loading="lazy" />
+                    <Show when={isBlacklisted()}>
+                      <div class="md-map-overlay">
+                        <div class="md-map-x">✕</div>
+                      </div>
+                    </Show>
+                  </div>
+                  <span class={`md-map-name ${isBlacklisted() ? "md-map-name-blocked" : ""}`}>{m.name}</span>
+                </button>
+              );
+            }}
+          </For>
+        </div>
+      </Show>
+
+      <Show when={tab() === "rules"}>
+        <div class="md-info">
+          <span class="md-hint">Dodge when every condition in a rule matches — the map blacklist is always checked first</span>
+          <label class="il-mode-autolock">
+            <input type="checkbox" checked={props.testMode} onChange={(e) => props.onTestModeChange(e.currentTarget.checked)} />
+            Test mode (never quit)
+          </label>
+        </div>
+        <div class="md-rules il-fade-in">
+          <DodgeRules maps={COMP_MAPS.map(m => m.name)} rules={props.rules} onChange={props.onRulesChange} />
+        </div>
+      </Show>
+
+      <Show when={tab() === "penalties"}>
+        <div class="md-rules il-fade-in">
+          <div class="md-penalty">
+            <Show when={report()} fallback={<span class="md-hint">Connect to Valorant to see your dodge history</span>}>
+              {(r) => (
+                <>
+                  <div class="st-timing-preview">
+                    <div class="st-timing-item">
+                      <span class="st-timing-label">Dodges (24h)</span>
+                      <span class="st-timing-val">{r().estimate.dodges24h}</span>
+                    </div>
+                    <div class="st-timing-divider" />
+                    <div class="st-timing-item">
+                      <span class="st-timing-label">Next Timeout</span>
+                      <span class="st-timing-val">~{r().estimate.nextTimeoutMinutes}m</span>
+                    </div>
+                    <div class="st-timing-divider" />
+                    <div class="st-timing-item">
+                      <span class="st-timing-label">Next RR Loss</span>
+                      <span class="st-timing-val">~{r().estimate.nextRrLoss} RR</span>
                     </div>
+                  </div>
+                  <Show when={r().restrictions}>
+                    {(x) => (
+                      <span class={`md-hint ${x().queueRestricted ? "md-penalty-active" : ""}`}>
+                        {x().activePenalties > 0
+                          ? `${x().activePenalties} active penalt${x().activePenalties === 1 ? "y" : "ies"}${x().expiresAt ? ` until ${formatTime(x().expiresAt!)}` : ""}`
+                          : "No active penalties on your account"}
+                      </span>
+                    )}
                   </Show>
+                  <span class="md-hint">Estimates assume Riot's escalating timeouts; RR loss only applies in Competitive.</span>
+                </>
+              )}
+            </Show>
+          </div>
+
+          <div class="md-info">
+            <label class="il-mode-autolock">
+              Allow at most
+              <input
+                type="number"
+                class="il-mode-secs"
+                min={0}
+                max={20}
+                value={props.dodgeCap.maxAutoDodges}
+                onChange={(e) => props.onDodgeCapChange({ ...props.dodgeCap, maxAutoDodges: Math.max(0, parseInt(e.currentTarget.value) || 0) })}
+              />
+              automatic dodges every
+              <input
+                type="number"
+                class="il-mode-secs"
+                min={1}
+                max={168}
+                value={props.dodgeCap.dodgeWindowHours}
+                onChange={(e) => props.onDodgeCapChange({ ...props.dodgeCap, dodgeWindowHours: Math.max(1, parseInt(e.currentTarget.value) || 24) })}
+              />
+              hours (0 = no limit)
+            </label>
+            <Show when={report() && props.dodgeCap.maxAutoDodges > 0}>
+              <span class="md-hint">{report()!.autoDodgesInWindow}/{props.dodgeCap.maxAutoDodges} used</span>
+            </Show>
+          </div>
+
+          <div class="md-dodge-list">
+            <For each={report()?.recent ?? []} fallback={<span class="md-hint">No dodges recorded yet</span>}>
+              {(d) => (
+                <div class="md-dodge-row">
+                  <span class="md-dodge-time">{formatTime(d.timestamp)}</span>
+                  <span class="md-dodge-map">{d.mapName || "Unknown map"}</span>
+                  <span class="md-dodge-rule">{d.automatic ? d.rule : "Manual"}</span>
                 </div>
-                <span class={`md-map-name ${isBlacklisted() ? "md-map-name-blocked" : ""}`}>{m.name}</span>
-              </button>
-            );
-          }}
-        </For>
-      </div>
+              )}
+            </For>
+          </div>
+        </div>
+      </Show>
 
       <div class="il-status-banner">
         <div class={`il-status-dot ${
           !props.active ? "il-status-dot-inactive" :
           dodgeStatus() === "dodged" ? "il-status-dot-locked" :
-          dodgeStatus() === "dodging" ? "il-status-dot-locking" :
-          pregame() ? "il-status-dot-locking" :
+          dodgeStatus() === "dodging" || dodgeStatus() === "test" ? "il-status-dot-locking" :
+          inPregame() ? "il-status-dot-locking" :
           "il-status-dot-waiting"
         }`} />
         <span class="il-status-text">
           <Show when={!props.active}>Map dodge is inactive</Show>
-          <Show when={props.active && blacklisted().size === 0 && !pregame()}>No maps blacklisted</Show>
-          <Show when={props.active && blacklisted().size > 0 && dodgeStatus() === "idle" && !pregame()}>
+          <Show when={props.active && blacklisted().size === 0 && activeRules() === 0 && !inPregame()}>No maps blacklisted</Show>
+          <Show when={props.active && (blacklisted().size > 0 || activeRules() > 0) && !inPregame()}>
             Waiting for match — dodging {blacklisted().size} map{blacklisted().size !== 1 ? "s" : ""}
+            {activeRules() > 0 ? ` and ${activeRules()} rule${activeRules() !== 1 ? "s" : ""}` : ""}
+            {props.testMode ? " (test mode)" : ""}
           </Show>
           <Show when={props.active && dodgeStatus() === "dodging"}>
-            Dodging <span class="il-status-agent">{pregame()?.mapName}</span>...
+            Dodging <span class="il-status-agent">{dodge()!.mapName}</span> — {dodge()!.message}
           </Show>
           <Show when={props.active && dodgeStatus() === "dodged"}>
-            Dodged <span class="il-status-agent">{pregame()?.mapName}</span>
+            {dryRun() ? "Would have dodged" : "Dodged"} <span class="il-status-agent">{dodge()!.mapName}</span> — {dodge()!.message}
+          </Show>
+          <Show when={props.active && (dodgeStatus() === "disabled" || dodgeStatus() === "failed" || dodgeStatus() === "test" || dodgeStatus() === "capped")}>
+            {dodge()!.message}
           </Show>
-          <Show when={props.active && dodgeStatus() === "idle" && pregame() && !blacklisted().has(pregame()!.mapName)}>
-            In agent select on <span class="il-status-agent">{pregame()!.mapName}</span> — safe map
+          <Show when={props.active && dodgeStatus() === "safe"}>
+            In agent select on <span class="il-status-agent">{dodge()!.mapName}</span> — safe map
           </Show>
         </span>
+        <Show when={dryRun()}>
+          <span class="il-dry-run">Dry run</span>
+        </Show>
       </div>
     </div>
   );
@@ -1,8 +1,10 @@
-import { Component, Show, For, createSignal, createEffect, on, onCleanup } from "solid-js";
+import { Component, Show, For, createSignal, createEffect, on, onMount, onCleanup } from "solid-js";
 import { TbOutlineCrown, TbOutlineX, TbOutlineCopy, TbOutlineCheck, TbOutlineHash, TbOutlineTrash, TbOutlineChevronDown } from "solid-icons/tb";
-import { getParty, partyInvite, partyKick, partyPromote, partySetAccessibility, partySetReady, getFriends, partyGenerateCode, partyDisableCode, partyAcceptInvite, partyDeclineInvite, partySetQueue } from "@src/ipc/commands";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import { getParty, getWatchlist, partyInvite, partyKick, partyPromote, partySetAccessibility, partySetReady, getFriends, getLivePresences, partyGenerateCode, partyDisableCode, partyAcceptInvite, partyDeclineInvite, partySetQueue } from "@src/ipc/commands";
 import { rankName, rankIcon } from "@src/utils/ranks";
-import type { ConnectionStatus, PartyState, PartyMember, PartyInvite, Friend } from "@src/types/valorant";
+import WatchlistEditor, { WatchTags } from "../components/WatchlistEditor";
+import type { ConnectionStatus, PartyState, PartyMember, PartyInvite, Friend, LivePresence, PresenceChange, WatchEntry } from "@src/types/valorant";
 
 interface PartyProps {
   status: ConnectionStatus;
@@ -25,6 +27,8 @@ const MemberCard: Component<{
   isSelf: boolean;
   partyId: string;
   onRefresh: () => void;
+  watchEntry: WatchEntry | null;
+  onWatchlistChange: () => void;
 }> = (props) => {
   const m = () => props.member;
   const [kicking, setKicking] = createSignal(false);
@@ -73,6 +77,16 @@ const MemberCard: Component<{
             <Show when={m().isOwner}>
               <TbOutlineCrown size={12} class="pt-crown" />
             </Show>
+            <WatchTags tags={props.watchEntry?.tags ?? []} />
+            <Show when={!props.isSelf}>
+              <WatchlistEditor
+                puuid={m().puuid}
+                gameName={m().gameName}
+                tagLine={m().tagLine}
+                entry={props.watchEntry}
+                onChange={props.onWatchlistChange}
+              />
+            </Show>
           </div>
           <div class="pt-member-sub">
             <Show when={m().rank > 0} fallback={<span class="pt-member-rank-text">Unranked</span>}>
@@ -120,7 +134,10 @@ const Party: Component<PartyProps> = (props) => {
   const [queueOpen, setQueueOpen] = createSignal(false);
   const [queueLoading, setQueueLoading] = createSignal(false);
   const [inviteActions, setInviteActions] = createSignal<Record<string, "accepting" | "declining">>({});
+  const [watchlist, setWatchlist] = createSignal<WatchEntry[]>([]);
+  const [presences, setPresences] = createSignal<Record<string, LivePresence>>({});
   let pollInterval: ReturnType<typeof setInterval> | null = null;
+  let unlistenPresence: UnlistenFn | null = null;
 
   const fetchParty = async () => {
     if (props.status !== "connected") {
@@ -143,8 +160,22 @@ const Party: Component<PartyProps> = (props) => {
     } catch {}
   };
 
+  const fetchPresences = () => {
+    getLivePresences()
+      .then(list => setPresences(Object.fromEntries(list.map(p => [p.puuid, p]))))
+      .catch(() => {});
+  };
+
+  const fetchWatchlist = () => {
+    getWatchlist().then(setWatchlist).catch(() => {});
+  };
+
+  const watchEntry = (puuid: string) => watchlist().find(e => e.puuid === puuid) ?? null;
+
   const refresh = async () => {
     setLoading(true);
+    fetchWatchlist();
+    fetchPresences();
     await Promise.all([fetchParty(), fetchFriendsList()]);
     setLoading(false);
   };
@@ -160,8 +191,17 @@ const Party: Component<PartyProps> = (props) => {
     }
   }));
 
+  onMount(async () => {
+    const fn = await listen<PresenceChange>("presence-update", (event) => {
+      const p = event.payload.presence;
+      setPresences(prev => ({ ...prev, [p.puuid]: p }));
+    });
+    unlistenPresence = fn;
+  });
+
   onCleanup(() => {
     if (pollInterval) clearInterval(pollInterval);
+    unlistenPresence?.();
   });
 
   const partyPuuids = () => new Set(party()?.members.map(m => m.puuid) || []);
@@ -389,6 +429,8 @@ const Party: Component<PartyProps> = (props) => {
                       isSelf={member.isOwner && p().isOwner || false}
                       partyId={p().partyId}
                       onRefresh={fetchParty}
+                      watchEntry={watchEntry(member.puuid)}
+                      onWatchlistChange={fetchWatchlist}
                     />
                   )}
                 </For>
@@ -445,6 +487,12 @@ const Party: Component<PartyProps> = (props) => {
                           ? `https://media.valorant-api.com/playercards/${friend.playerCardId}/wideart.png`
                           : null;
                         const statusLabel = () => {
+                          const live = presences()[friend.puuid];
+                          if (live?.sessionLoopState === "INGAME" && live.mapName) {
+                            return `In Game · ${live.mapName} ${live.allyScore}–${live.enemyScore}`;
+                          }
+                          if (live?.sessionLoopState === "PREGAME") return "Agent Select";
+                          if (live?.inParty) return `In Party (${live.partySize}/${live.maxPartySize || 5})`;
                           if (friend.status === "dnd") return "In Game";
                           if (friend.status === "away") return "Away";
                           return "Online";
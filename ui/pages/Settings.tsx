@@ -1,71 +1,201 @@
-import { Component, Show, createSignal, createEffect } from "solid-js";
-import { TbOutlineBolt, TbOutlineUser, TbOutlineAdjustments, TbOutlineWindow, TbOutlineEyeOff, TbOutlineBrandDiscord } from "solid-icons/tb";
-
-export type TimingPreset = "instant" | "humanized" | "custom";
-
-export interface InstalockTiming {
-  preset: TimingPreset;
-  selectDelay: number;
-  lockDelay: number;
-}
+import { Component, Show, For, createSignal, onMount, onCleanup } from "solid-js";
+import { listen } from "@tauri-apps/api/event";
+import { TbOutlineBolt, TbOutlineUser, TbOutlineAdjustments, TbOutlineWindow, TbOutlineEyeOff, TbOutlineBrandDiscord, TbOutlineBell, TbOutlineScoreboard, TbOutlineClock, TbOutlineHistory, TbOutlineFlask, TbOutlineStack2 } from "solid-icons/tb";
+import { exportMatches, exportSessions, replayPregame, createProfile, cloneProfile, deleteProfile, activateProfile, setProfileActivation } from "@src/ipc/commands";
+import type { AuditEntry } from "@src/types/valorant";
+import type { ExportFormat, ExportFilter, TimingPreset, DelayDistribution, TimingConfig, AuditLogConfig, Profile, ProfileSwitch } from "@src/ipc/commands";
+import AutomationLog from "../components/AutomationLog";
+import ProfileActivation from "../components/ProfileActivation";
 
 const PRESET_DESCRIPTIONS: Record<TimingPreset, string> = {
   instant: "Locks agent immediately with zero delay",
-  humanized: "Adds natural-feeling delays to mimic human behavior",
-  custom: "Set your own select and lock delay timings",
+  humanized: "Randomised delays around 700ms to select and 450ms to lock",
+  custom: "Pick fixed, uniform or normal delays for select and lock",
+  deadline: "Waits and locks with a few seconds left on the timer",
 };
 
-const PRESET_VALUES: Record<Exclude<TimingPreset, "custom">, { select: number; lock: number }> = {
-  instant: { select: 0, lock: 0 },
-  humanized: { select: 800, lock: 1200 },
+const DEFAULT_DISTRIBUTIONS: Record<DelayDistribution["kind"], DelayDistribution> = {
+  fixed: { kind: "fixed", ms: 500 },
+  uniform: { kind: "uniform", min: 300, max: 1000 },
+  normal: { kind: "normal", mean: 700, stdDev: 200, min: 300, max: 1400 },
 };
 
+const DelaySlider: Component<{ label: string; value: number; max: number; step: number; format: (v: number) => string; onChange: (v: number) => void }> = (props) => (
+  <div class="st-slider-group">
+    <div class="st-slider-header">
+      <span class="st-slider-label">{props.label}</span>
+      <span class="st-slider-value">{props.format(props.value)}</span>
+    </div>
+    <input
+      type="range"
+      class="st-slider"
+      min={0}
+      max={props.max}
+      step={props.step}
+      value={props.value}
+      onInput={(e) => props.onChange(parseInt(e.currentTarget.value))}
+    />
+    <div class="st-slider-range">
+      <span>{props.format(0)}</span>
+      <span>{props.format(props.max)}</span>
+    </div>
+  </div>
+);
+
+const DistributionEditor: Component<{ label: string; value: DelayDistribution; format: (v: number) => string; onChange: (v: DelayDistribution) => void }> = (props) => {
+  const set = (patch: Partial<Record<string, number>>) => props.onChange({ ...props.value, ...patch } as DelayDistribution);
+
+  return (
+    <div class="st-distribution">
+      <div class="st-distribution-header">
+        <span class="st-slider-label">{props.label}</span>
+        <div class="st-export-formats">
+          <For each={["fixed", "uniform", "normal"] as const}>
+            {(kind) => (
+              <button
+                class={`st-export-format ${props.value.kind === kind ? "st-export-format-active" : ""}`}
+                onClick={() => props.value.kind !== kind && props.onChange(DEFAULT_DISTRIBUTIONS[kind])}
+              >
+                {kind.charAt(0).toUpperCase() + kind.slice(1)}
+              </button>
+            )}
+          </For>
+        </div>
+      </div>
+      <Show when={props.value.kind === "fixed" && props.value}>
+        {(d) => <DelaySlider label="Delay" value={d().ms} max={5000} step={50} format={props.format} onChange={(ms) => set({ ms })} />}
+      </Show>
+      <Show when={props.value.kind === "uniform" && props.value}>
+        {(d) => (
+          <>
+            <DelaySlider label="Minimum" value={d().min} max={5000} step={50} format={props.format} onChange={(min) => set({ min, max: Math.max(min, d().max) })} />
+            <DelaySlider label="Maximum" value={d().max} max={5000} step={50} format={props.format} onChange={(max) => set({ max, min: Math.min(max, d().min) })} />
+          </>
+        )}
+      </Show>
+      <Show when={props.value.kind === "normal" && props.value}>
+        {(d) => (
+          <>
+            <DelaySlider label="Mean" value={d().mean} max={5000} step={50} format={props.format} onChange={(mean) => set({ mean })} />
+            <DelaySlider label="Std Deviation" value={d().stdDev} max={2000} step={25} format={props.format} onChange={(stdDev) => set({ stdDev })} />
+            <DelaySlider label="Clamp Min" value={d().min} max={5000} step={50} format={props.format} onChange={(min) => set({ min, max: Math.max(min, d().max) })} />
+            <DelaySlider label="Clamp Max" value={d().max} max={5000} step={50} format={props.format} onChange={(max) => set({ max, min: Math.min(max, d().min) })} />
+          </>
+        )}
+      </Show>
+    </div>
+  );
+};
+
+const EXPORT_QUEUES: { id: string; label: string }[] = [
+  { id: "", label: "All Queues" },
+  { id: "competitive", label: "Competitive" },
+  { id: "unrated", label: "Unrated" },
+  { id: "swiftplay", label: "Swiftplay" },
+  { id: "premier", label: "Premier" },
+  { id: "spikerush", label: "Spike Rush" },
+  { id: "deathmatch", label: "Deathmatch" },
+];
+
 interface SettingsProps {
-  instalockTiming: InstalockTiming;
-  onTimingChange: (timing: InstalockTiming) => void;
+  instalockTiming: TimingConfig;
+  onTimingChange: (timing: TimingConfig) => void;
+  profiles: Profile[];
+  activeProfile: string | null;
+  dryRun: boolean;
+  onDryRunChange: (v: boolean) => void;
+  auditLog: AuditLogConfig;
+  onAuditLogChange: (v: AuditLogConfig) => void;
   minimizeOnClose: boolean;
   onMinimizeOnCloseChange: (v: boolean) => void;
   startMinimized: boolean;
   onStartMinimizedChange: (v: boolean) => void;
+  postMatchNotifications: boolean;
+  onPostMatchNotificationsChange: (v: boolean) => void;
   discordEnabled: boolean;
   onDiscordEnabledChange: (v: boolean) => void;
   discordDetails: string;
   onDiscordDetailsChange: (v: string) => void;
   discordState: string;
   onDiscordStateChange: (v: string) => void;
+  discordShowScore: boolean;
+  onDiscordShowScoreChange: (v: boolean) => void;
 }
 
 const Settings: Component<SettingsProps> = (props) => {
-  const [preset, setPreset] = createSignal<TimingPreset>(props.instalockTiming.preset);
-  const [selectDelay, setSelectDelay] = createSignal(props.instalockTiming.selectDelay);
-  const [lockDelay, setLockDelay] = createSignal(props.instalockTiming.lockDelay);
-
-  createEffect(() => {
-    setPreset(props.instalockTiming.preset);
-    setSelectDelay(props.instalockTiming.selectDelay);
-    setLockDelay(props.instalockTiming.lockDelay);
-  });
+  const timing = () => props.instalockTiming;
+  const updateTiming = (patch: Partial<TimingConfig>) => props.onTimingChange({ ...timing(), ...patch });
+
+  const updateAudit = (patch: Partial<AuditLogConfig>) => props.onAuditLogChange({ ...props.auditLog, ...patch });
 
-  const handlePresetChange = (p: TimingPreset) => {
-    setPreset(p);
-    if (p === "custom") {
-      props.onTimingChange({ preset: p, selectDelay: selectDelay(), lockDelay: lockDelay() });
-    } else {
-      const vals = PRESET_VALUES[p];
-      setSelectDelay(vals.select);
-      setLockDelay(vals.lock);
-      props.onTimingChange({ preset: p, selectDelay: vals.select, lockDelay: vals.lock });
+  const [profileName, setProfileName] = createSignal("");
+  const [profileError, setProfileError] = createSignal("");
+  const [expandedProfile, setExpandedProfile] = createSignal<string | null>(null);
+  const [lastSwitch, setLastSwitch] = createSignal<ProfileSwitch | null>(null);
+
+  const runProfile = async (action: () => Promise<unknown>) => {
+    setProfileError("");
+    try {
+      await action();
+    } catch (e) {
+      setProfileError(String(e));
     }
   };
 
-  const handleSelectDelay = (val: number) => {
-    setSelectDelay(val);
-    props.onTimingChange({ preset: "custom", selectDelay: val, lockDelay: lockDelay() });
+  const saveProfile = () => runProfile(async () => {
+    await createProfile(profileName());
+    setProfileName("");
+  });
+
+  const [dryRunEvents, setDryRunEvents] = createSignal<AuditEntry[]>([]);
+  const [replayStatus, setReplayStatus] = createSignal<{ ok: boolean; message: string } | null>(null);
+
+  const runReplay = async () => {
+    setReplayStatus(null);
+    try {
+      const result = await replayPregame();
+      const parts = [`instalock ${result.instalock.state}`, `dodge ${result.dodge.state}`];
+      setReplayStatus({ ok: true, message: `Replayed the last lobby — ${parts.join(", ")}` });
+    } catch (e) {
+      setReplayStatus({ ok: false, message: String(e) });
+    }
   };
 
-  const handleLockDelay = (val: number) => {
-    setLockDelay(val);
-    props.onTimingChange({ preset: "custom", selectDelay: selectDelay(), lockDelay: val });
+  onMount(() => {
+    const unlisten = listen<AuditEntry>("automation-dry-run", (event) => {
+      setDryRunEvents(prev => [event.payload, ...prev].slice(0, 20));
+    });
+    const unlistenSwitch = listen<ProfileSwitch>("profile-switched", (event) => setLastSwitch(event.payload));
+    onCleanup(() => {
+      unlisten.then(fn => fn());
+      unlistenSwitch.then(fn => fn());
+    });
+  });
+
+  const [exportFormat, setExportFormat] = createSignal<ExportFormat>("csv");
+  const [exportFrom, setExportFrom] = createSignal("");
+  const [exportTo, setExportTo] = createSignal("");
+  const [exportQueue, setExportQueue] = createSignal("");
+  const [exportMap, setExportMap] = createSignal("");
+  const [exportStatus, setExportStatus] = createSignal<{ ok: boolean; message: string } | null>(null);
+
+  const exportFilter = (): ExportFilter => ({
+    from: exportFrom() ? new Date(`${exportFrom()}T00:00:00`).getTime() : null,
+    to: exportTo() ? new Date(`${exportTo()}T23:59:59`).getTime() : null,
+    queue: exportQueue() || null,
+    map: exportMap().trim() || null,
+  });
+
+  const runExport = async (kind: "matches" | "sessions") => {
+    setExportStatus(null);
+    try {
+      const path = kind === "matches"
+        ? await exportMatches(exportFormat(), exportFilter())
+        : await exportSessions(exportFormat(), exportFilter());
+      setExportStatus({ ok: true, message: `Saved to ${path}` });
+    } catch (e) {
+      setExportStatus({ ok: false, message: String(e) });
+    }
   };
 
   const formatMs = (ms: number) => {
@@ -81,6 +211,68 @@ const Settings: Component<SettingsProps> = (props) => {
         <p class="settings-desc">Configure Downfall preferences</p>
       </div>
 
+      <div class="settings-section st-fade-in">
+        <div class="settings-section-header">
+          <h2 class="settings-section-title">Profiles</h2>
+          <p class="settings-section-desc">Bundle instalock, map dodge, timing and Discord settings and switch between them in one click</p>
+        </div>
+
+        <div class="st-profiles">
+          <For each={props.profiles}>
+            {(profile) => (
+              <div class={`st-profile-item ${props.activeProfile === profile.id ? "st-profile-active" : ""}`}>
+                <div class="st-profile">
+                  <div class="st-toggle-icon"><TbOutlineStack2 size={16} /></div>
+                  <span class="st-profile-name">{profile.name}</span>
+                  <Show when={profile.activation.enabled}>
+                    <span class="st-profile-badge st-profile-badge-auto">Auto</span>
+                  </Show>
+                  <Show
+                    when={props.activeProfile === profile.id}
+                    fallback={<button class="st-profile-btn" onClick={() => runProfile(() => activateProfile(profile.id))}>Activate</button>}
+                  >
+                    <span class="st-profile-badge">Active</span>
+                  </Show>
+                  <button
+                    class="st-profile-btn"
+                    onClick={() => setExpandedProfile(expandedProfile() === profile.id ? null : profile.id)}
+                  >
+                    Schedule
+                  </button>
+                  <button class="st-profile-btn" onClick={() => runProfile(() => cloneProfile(profile.id))}>Clone</button>
+                  <button class="dr-remove" onClick={() => runProfile(() => deleteProfile(profile.id))}>×</button>
+                </div>
+                <Show when={expandedProfile() === profile.id}>
+                  <ProfileActivation
+                    activation={profile.activation}
+                    onChange={(activation) => runProfile(() => setProfileActivation(profile.id, activation))}
+                  />
+                </Show>
+              </div>
+            )}
+          </For>
+        </div>
+
+        <Show when={lastSwitch()}>
+          {(s) => <span class="st-export-status">Switched to {s().name} — {s().reason}</span>}
+        </Show>
+
+        <div class="st-profile-new">
+          <input
+            type="text"
+            class="st-input"
+            value={profileName()}
+            onInput={(e) => setProfileName(e.currentTarget.value)}
+            onKeyDown={(e) => e.key === "Enter" && saveProfile()}
+            placeholder="Ranked grind, Chill with friends..."
+          />
+          <button class="st-export-btn" onClick={saveProfile}>Save Current Settings</button>
+        </div>
+        <Show when={profileError()}>
+          <span class="st-export-status st-export-status-error">{profileError()}</span>
+        </Show>
+      </div>
+
       <div class="settings-section st-fade-in">
         <div class="settings-section-header">
           <h2 class="settings-section-title">Instalock Timing</h2>
@@ -89,8 +281,8 @@ const Settings: Component<SettingsProps> = (props) => {
 
         <div class="st-presets">
           <button
-            class={`st-preset ${preset() === "instant" ? "st-preset-active" : ""}`}
-            onClick={() => handlePresetChange("instant")}
+            class={`st-preset ${timing().preset === "instant" ? "st-preset-active" : ""}`}
+            onClick={() => updateTiming({ preset: "instant" })}
           >
             <div class="st-preset-icon"><TbOutlineBolt size={18} /></div>
             <div class="st-preset-info">
@@ -99,8 +291,8 @@ const Settings: Component<SettingsProps> = (props) => {
             </div>
           </button>
           <button
-            class={`st-preset ${preset() === "humanized" ? "st-preset-active" : ""}`}
-            onClick={() => handlePresetChange("humanized")}
+            class={`st-preset ${timing().preset === "humanized" ? "st-preset-active" : ""}`}
+            onClick={() => updateTiming({ preset: "humanized" })}
           >
             <div class="st-preset-icon"><TbOutlineUser size={18} /></div>
             <div class="st-preset-info">
@@ -109,8 +301,8 @@ const Settings: Component<SettingsProps> = (props) => {
             </div>
           </button>
           <button
-            class={`st-preset ${preset() === "custom" ? "st-preset-active" : ""}`}
-            onClick={() => handlePresetChange("custom")}
+            class={`st-preset ${timing().preset === "custom" ? "st-preset-active" : ""}`}
+            onClick={() => updateTiming({ preset: "custom" })}
           >
             <div class="st-preset-icon"><TbOutlineAdjustments size={18} /></div>
             <div class="st-preset-info">
@@ -118,61 +310,43 @@ const Settings: Component<SettingsProps> = (props) => {
               <span class="st-preset-desc">{PRESET_DESCRIPTIONS.custom}</span>
             </div>
           </button>
+          <button
+            class={`st-preset ${timing().preset === "deadline" ? "st-preset-active" : ""}`}
+            onClick={() => updateTiming({ preset: "deadline" })}
+          >
+            <div class="st-preset-icon"><TbOutlineClock size={18} /></div>
+            <div class="st-preset-info">
+              <span class="st-preset-name">Deadline</span>
+              <span class="st-preset-desc">{PRESET_DESCRIPTIONS.deadline}</span>
+            </div>
+          </button>
         </div>
 
-        <Show when={preset() === "custom"}>
+        <Show when={timing().preset === "custom"}>
           <div class="st-sliders st-fade-in">
-            <div class="st-slider-group">
-              <div class="st-slider-header">
-                <span class="st-slider-label">Select Delay</span>
-                <span class="st-slider-value">{formatMs(selectDelay())}</span>
-              </div>
-              <input
-                type="range"
-                class="st-slider"
-                min={0}
-                max={5000}
-                step={50}
-                value={selectDelay()}
-                onInput={(e) => handleSelectDelay(parseInt(e.currentTarget.value))}
-              />
-              <div class="st-slider-range">
-                <span>0ms</span>
-                <span>5s</span>
-              </div>
-            </div>
-            <div class="st-slider-group">
-              <div class="st-slider-header">
-                <span class="st-slider-label">Lock Delay</span>
-                <span class="st-slider-value">{formatMs(lockDelay())}</span>
-              </div>
-              <input
-                type="range"
-                class="st-slider"
-                min={0}
-                max={5000}
-                step={50}
-                value={lockDelay()}
-                onInput={(e) => handleLockDelay(parseInt(e.currentTarget.value))}
-              />
-              <div class="st-slider-range">
-                <span>0ms</span>
-                <span>5s</span>
-              </div>
-            </div>
+            <DistributionEditor label="Select Delay" value={timing().select} format={formatMs} onChange={(select) => updateTiming({ select })} />
+            <DistributionEditor label="Lock Delay" value={timing().lock} format={formatMs} onChange={(lock) => updateTiming({ lock })} />
+            <DelaySlider label="Minimum Select → Lock Gap" value={timing().minGap} max={2000} step={25} format={formatMs} onChange={(minGap) => updateTiming({ minGap })} />
           </div>
         </Show>
 
-        <Show when={preset() === "instant"}>
+        <Show when={timing().preset === "deadline"}>
+          <div class="st-sliders st-fade-in">
+            <DelaySlider label="Lock With Time Left" value={timing().deadlineSecs} max={60} step={1} format={(v) => `${v}s`} onChange={(deadlineSecs) => updateTiming({ deadlineSecs })} />
+            <DelaySlider label="Minimum Select → Lock Gap" value={timing().minGap} max={2000} step={25} format={formatMs} onChange={(minGap) => updateTiming({ minGap })} />
+          </div>
+        </Show>
+
+        <Show when={timing().preset === "instant"}>
           <div class="st-timing-preview st-fade-in">
             <div class="st-timing-item">
               <span class="st-timing-label">Select Delay</span>
-              <span class="st-timing-val">{formatMs(selectDelay())}</span>
+              <span class="st-timing-val">{formatMs(0)}</span>
             </div>
             <div class="st-timing-divider" />
             <div class="st-timing-item">
               <span class="st-timing-label">Lock Delay</span>
-              <span class="st-timing-val">{formatMs(lockDelay())}</span>
+              <span class="st-timing-val">{formatMs(0)}</span>
             </div>
           </div>
         </Show>
@@ -215,6 +389,21 @@ const Settings: Component<SettingsProps> = (props) => {
               <div class="st-switch-thumb" />
             </button>
           </div>
+          <div class="st-toggle-row">
+            <div class="st-toggle-info">
+              <div class="st-toggle-icon"><TbOutlineBell size={16} /></div>
+              <div class="st-toggle-text">
+                <span class="st-toggle-label">Post-Match Summary</span>
+                <span class="st-toggle-desc">Notify with result, score, KDA and RR when a match ends</span>
+              </div>
+            </div>
+            <button
+              class={`st-switch ${props.postMatchNotifications ? "st-switch-on" : ""}`}
+              onClick={() => props.onPostMatchNotificationsChange(!props.postMatchNotifications)}
+            >
+              <div class="st-switch-thumb" />
+            </button>
+          </div>
         </div>
       </div>
 
@@ -240,6 +429,23 @@ const Settings: Component<SettingsProps> = (props) => {
               <div class="st-switch-thumb" />
             </button>
           </div>
+          <Show when={props.discordEnabled}>
+            <div class="st-toggle-row">
+              <div class="st-toggle-info">
+                <div class="st-toggle-icon"><TbOutlineScoreboard size={16} /></div>
+                <div class="st-toggle-text">
+                  <span class="st-toggle-label">Show Match Score</span>
+                  <span class="st-toggle-desc">Replace the state text with map, mode and score while in a match</span>
+                </div>
+              </div>
+              <button
+                class={`st-switch ${props.discordShowScore ? "st-switch-on" : ""}`}
+                onClick={() => props.onDiscordShowScoreChange(!props.discordShowScore)}
+              >
+                <div class="st-switch-thumb" />
+              </button>
+            </div>
+          </Show>
         </div>
 
         <Show when={props.discordEnabled}>
@@ -267,6 +473,157 @@ const Settings: Component<SettingsProps> = (props) => {
           </div>
         </Show>
       </div>
+
+      <div class="settings-section st-fade-in">
+        <div class="settings-section-header">
+          <h2 class="settings-section-title">Dry Run</h2>
+          <p class="settings-section-desc">Run instalock and map dodge against live lobbies without selecting, locking or quitting</p>
+        </div>
+
+        <div class="st-toggles">
+          <div class="st-toggle-row">
+            <div class="st-toggle-info">
+              <div class="st-toggle-icon"><TbOutlineFlask size={16} /></div>
+              <div class="st-toggle-text">
+                <span class="st-toggle-label">Dry Run Mode</span>
+                <span class="st-toggle-desc">Report what the engine would have done instead of doing it</span>
+              </div>
+            </div>
+            <button
+              class={`st-switch ${props.dryRun ? "st-switch-on" : ""}`}
+              onClick={() => props.onDryRunChange(!props.dryRun)}
+            >
+              <div class="st-switch-thumb" />
+            </button>
+          </div>
+        </div>
+
+        <div class="st-dry-run-actions">
+          <button class="st-export-btn" onClick={runReplay}>Replay Last Lobby</button>
+          <Show when={replayStatus()}>
+            {(status) => (
+              <span class={`st-export-status ${status().ok ? "" : "st-export-status-error"}`}>{status().message}</span>
+            )}
+          </Show>
+        </div>
+
+        <Show when={dryRunEvents().length > 0}>
+          <div class="al-log">
+            <For each={dryRunEvents()}>
+              {(e) => (
+                <div class="al-entry">
+                  <div class="al-entry-main">
+                    <span class="al-entry-action">{e.feature} · {e.action}</span>
+                    <span class="al-entry-message">{e.message}</span>
+                  </div>
+                  <div class="al-entry-meta">
+                    <span>{new Date(e.timestamp).toLocaleTimeString()}</span>
+                    <span>{e.delayMs}ms wait</span>
+                  </div>
+                </div>
+              )}
+            </For>
+          </div>
+        </Show>
+      </div>
+
+      <div class="settings-section st-fade-in">
+        <div class="settings-section-header">
+          <h2 class="settings-section-title">Automation Log</h2>
+          <p class="settings-section-desc">Every select, lock and dodge decision with its HTTP status and timings</p>
+        </div>
+
+        <div class="st-toggles">
+          <div class="st-toggle-row">
+            <div class="st-toggle-info">
+              <div class="st-toggle-icon"><TbOutlineHistory size={16} /></div>
+              <div class="st-toggle-text">
+                <span class="st-toggle-label">Record Decisions</span>
+                <span class="st-toggle-desc">Keep a persistent log of automated actions</span>
+              </div>
+            </div>
+            <button
+              class={`st-switch ${props.auditLog.enabled ? "st-switch-on" : ""}`}
+              onClick={() => updateAudit({ enabled: !props.auditLog.enabled })}
+            >
+              <div class="st-switch-thumb" />
+            </button>
+          </div>
+        </div>
+
+        <Show when={props.auditLog.enabled}>
+          <div class="st-inputs st-fade-in">
+            <div class="st-input-group">
+              <label class="st-input-label">Keep Entries</label>
+              <input
+                type="number"
+                class="st-input"
+                min={50}
+                value={props.auditLog.maxEntries}
+                onChange={(e) => updateAudit({ maxEntries: Math.max(50, parseInt(e.currentTarget.value) || 2000) })}
+              />
+            </div>
+            <div class="st-input-group">
+              <label class="st-input-label">Retention (days, 0 = forever)</label>
+              <input
+                type="number"
+                class="st-input"
+                min={0}
+                value={props.auditLog.retentionDays}
+                onChange={(e) => updateAudit({ retentionDays: Math.max(0, parseInt(e.currentTarget.value) || 0) })}
+              />
+            </div>
+          </div>
+        </Show>
+
+        <AutomationLog />
+      </div>
+
+      <div class="settings-section st-fade-in">
+        <div class="settings-section-header">
+          <h2 class="settings-section-title">Data Export</h2>
+          <p class="settings-section-desc">Export stored match history and session summaries for spreadsheets</p>
+        </div>
+
+        <div class="st-export-formats">
+          <button class={`st-export-format ${exportFormat() === "csv" ? "st-export-format-active" : ""}`} onClick={() => setExportFormat("csv")}>CSV</button>
+          <button class={`st-export-format ${exportFormat() === "json" ? "st-export-format-active" : ""}`} onClick={() => setExportFormat("json")}>JSON</button>
+        </div>
+
+        <div class="st-export-filters">
+          <div class="st-input-group">
+            <label class="st-input-label">From</label>
+            <input type="date" class="st-input" value={exportFrom()} onInput={(e) => setExportFrom(e.currentTarget.value)} />
+          </div>
+          <div class="st-input-group">
+            <label class="st-input-label">To</label>
+            <input type="date" class="st-input" value={exportTo()} onInput={(e) => setExportTo(e.currentTarget.value)} />
+          </div>
+          <div class="st-input-group">
+            <label class="st-input-label">Queue</label>
+            <select class="st-input" value={exportQueue()} onChange={(e) => setExportQueue(e.currentTarget.value)}>
+              <For each={EXPORT_QUEUES}>
+                {(q) => <option value={q.id}>{q.label}</option>}
+              </For>
+            </select>
+          </div>
+          <div class="st-input-group">
+            <label class="st-input-label">Map</label>
+            <input type="text" class="st-input" value={exportMap()} onInput={(e) => setExportMap(e.currentTarget.value)} placeholder="Any map" />
+          </div>
+        </div>
+
+        <div class="st-export-actions">
+          <button class="st-export-btn" onClick={() => runExport("matches")}>Export Matches</button>
+          <button class="st-export-btn" onClick={() => runExport("sessions")}>Export Sessions</button>
+        </div>
+
+        <Show when={exportStatus()}>
+          {(status) => (
+            <span class={`st-export-status ${status().ok ? "" : "st-export-status-error"}`}>{status().message}</span>
+          )}
+        </Show>
+      </div>
     </div>
   );
 };